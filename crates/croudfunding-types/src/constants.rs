@@ -0,0 +1,184 @@
+//! Seeds, discriminators and byte offsets shared with the on-chain program.
+
+/// Seed prefix of campaign PDAs: `[CAMPAIGN_SEED, creator, campaign_seed_key(idempotency_key)]`
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+/// Seed prefix of donor record PDAs: `[DONOR_SEED, campaign, donor]`
+pub const DONOR_SEED: &[u8] = b"donor";
+/// Seed prefix of follow PDAs: `[FOLLOW_SEED, user, campaign]`
+pub const FOLLOW_SEED: &[u8] = b"follow";
+/// Seed prefix of organization donation PDAs: `[ORG_SEED, campaign, org]`
+pub const ORG_SEED: &[u8] = b"org";
+/// Seed prefix of campaign archive PDAs: `[ARCHIVE_SEED, campaign]`
+pub const ARCHIVE_SEED: &[u8] = b"archive";
+/// Seed prefix of pledge PDAs: `[PLEDGE_SEED, campaign, donor]`
+pub const PLEDGE_SEED: &[u8] = b"pledge";
+/// Seed prefix of attestation PDAs: `[ATTESTATION_SEED, issuer, subject]`
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+/// Seed of the program config singleton PDA: `[CONFIG_SEED]`
+pub const CONFIG_SEED: &[u8] = b"config";
+/// Seed prefix of dispute escrow PDAs: `[DISPUTE_ESCROW_SEED, campaign]`
+pub const DISPUTE_ESCROW_SEED: &[u8] = b"dispute_escrow";
+/// Seed prefix of audit log PDAs: `[AUDIT_LOG_SEED, campaign]`
+pub const AUDIT_LOG_SEED: &[u8] = b"audit_log";
+/// Seed prefix of donation message PDAs: `[MESSAGE_SEED, campaign, sequence as u64 little-endian]`
+pub const MESSAGE_SEED: &[u8] = b"message";
+/// Seed prefix of denylist entry PDAs: `[DENYLIST_SEED, authority, subject]`
+pub const DENYLIST_SEED: &[u8] = b"denylist";
+/// Seed prefix of quarantined donation PDAs: `[QUARANTINE_SEED, campaign, donor]`
+pub const QUARANTINE_SEED: &[u8] = b"quarantine";
+/// Seed prefix of waitlist entry PDAs: `[WAITLIST_SEED, campaign, donor]`
+pub const WAITLIST_SEED: &[u8] = b"waitlist";
+
+/// Anchor numbers custom errors from this offset, in declaration order
+pub const ERROR_CODE_OFFSET: u32 = 6000;
+
+/// Size of the Anchor discriminator at the start of every account and event
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// First 8 bytes of `sha256("account:Campaign")`
+pub const CAMPAIGN_DISCRIMINATOR: [u8; 8] = [50, 40, 49, 11, 157, 220, 229, 192];
+/// First 8 bytes of `sha256("account:DonorRecord")`
+pub const DONOR_RECORD_DISCRIMINATOR: [u8; 8] = [204, 101, 15, 37, 82, 141, 165, 40];
+/// First 8 bytes of `sha256("account:Follow")`
+pub const FOLLOW_DISCRIMINATOR: [u8; 8] = [222, 247, 253, 60, 70, 4, 164, 51];
+/// First 8 bytes of `sha256("account:AuditLog")`
+pub const AUDIT_LOG_DISCRIMINATOR: [u8; 8] = [230, 207, 176, 233, 170, 130, 101, 244];
+/// First 8 bytes of `sha256("account:OrgDonation")`
+pub const ORG_DONATION_DISCRIMINATOR: [u8; 8] = [238, 66, 153, 24, 81, 240, 109, 229];
+/// First 8 bytes of `sha256("account:CampaignArchive")`
+pub const CAMPAIGN_ARCHIVE_DISCRIMINATOR: [u8; 8] = [243, 84, 136, 72, 180, 9, 121, 110];
+/// First 8 bytes of `sha256("account:Pledge")`
+pub const PLEDGE_DISCRIMINATOR: [u8; 8] = [161, 197, 121, 46, 99, 75, 169, 131];
+/// First 8 bytes of `sha256("account:Attestation")`
+pub const ATTESTATION_DISCRIMINATOR: [u8; 8] = [152, 125, 183, 86, 36, 146, 121, 73];
+/// First 8 bytes of `sha256("account:ProgramConfig")`
+pub const PROGRAM_CONFIG_DISCRIMINATOR: [u8; 8] = [196, 210, 90, 231, 144, 149, 140, 63];
+/// First 8 bytes of `sha256("account:DisputeEscrow")`
+pub const DISPUTE_ESCROW_DISCRIMINATOR: [u8; 8] = [4, 51, 35, 202, 114, 140, 92, 65];
+/// First 8 bytes of `sha256("account:DonationMessage")`
+pub const DONATION_MESSAGE_DISCRIMINATOR: [u8; 8] = [214, 227, 239, 114, 216, 21, 116, 224];
+/// First 8 bytes of `sha256("account:DenylistEntry")`
+pub const DENYLIST_ENTRY_DISCRIMINATOR: [u8; 8] = [2, 44, 7, 103, 34, 229, 136, 179];
+/// First 8 bytes of `sha256("account:QuarantinedDonation")`
+pub const QUARANTINED_DONATION_DISCRIMINATOR: [u8; 8] = [11, 122, 154, 138, 180, 49, 200, 126];
+/// First 8 bytes of `sha256("account:WaitlistEntry")`
+pub const WAITLIST_ENTRY_DISCRIMINATOR: [u8; 8] = [115, 50, 18, 157, 178, 220, 23, 34];
+
+/// First 8 bytes of `sha256("event:CampaignDonationSent")`
+pub const CAMPAIGN_DONATION_SENT_DISCRIMINATOR: [u8; 8] = [62, 91, 218, 110, 208, 160, 92, 206];
+/// First 8 bytes of `sha256("event:CampaignDonationReceived")`
+pub const CAMPAIGN_DONATION_RECEIVED_DISCRIMINATOR: [u8; 8] = [156, 243, 95, 105, 250, 204, 56, 102];
+/// First 8 bytes of `sha256("event:CampaignCancelled")`
+pub const CAMPAIGN_CANCELLED_DISCRIMINATOR: [u8; 8] = [121, 171, 21, 55, 56, 234, 121, 44];
+/// First 8 bytes of `sha256("event:CampaignReopened")`
+pub const CAMPAIGN_REOPENED_DISCRIMINATOR: [u8; 8] = [184, 165, 238, 223, 148, 134, 15, 120];
+/// First 8 bytes of `sha256("event:DonationReceived")`
+pub const DONATION_RECEIVED_DISCRIMINATOR: [u8; 8] = [160, 135, 32, 7, 241, 105, 91, 158];
+/// First 8 bytes of `sha256("event:CampaignFinalized")`
+pub const CAMPAIGN_FINALIZED_DISCRIMINATOR: [u8; 8] = [219, 169, 142, 66, 105, 67, 124, 255];
+/// First 8 bytes of `sha256("event:DonationMatched")`
+pub const DONATION_MATCHED_DISCRIMINATOR: [u8; 8] = [143, 129, 80, 25, 184, 131, 32, 53];
+/// First 8 bytes of `sha256("event:MatchPoolFunded")`
+pub const MATCH_POOL_FUNDED_DISCRIMINATOR: [u8; 8] = [134, 204, 110, 12, 21, 239, 72, 64];
+/// First 8 bytes of `sha256("event:FundsMerged")`
+pub const FUNDS_MERGED_DISCRIMINATOR: [u8; 8] = [54, 233, 19, 113, 184, 248, 75, 79];
+/// First 8 bytes of `sha256("event:Reconciliation")`
+pub const RECONCILIATION_DISCRIMINATOR: [u8; 8] = [48, 221, 231, 82, 166, 159, 207, 79];
+/// First 8 bytes of `sha256("event:DisputeRaised")`
+pub const DISPUTE_RAISED_DISCRIMINATOR: [u8; 8] = [246, 167, 109, 37, 142, 45, 38, 176];
+/// First 8 bytes of `sha256("event:DisputeResolved")`
+pub const DISPUTE_RESOLVED_DISCRIMINATOR: [u8; 8] = [121, 64, 249, 153, 139, 128, 236, 187];
+/// First 8 bytes of `sha256("event:CampaignConfig")`
+pub const CAMPAIGN_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 87, 19, 15, 181, 216, 215, 9];
+/// First 8 bytes of `sha256("event:RivalryLinked")`
+pub const RIVALRY_LINKED_DISCRIMINATOR: [u8; 8] = [92, 209, 109, 47, 163, 231, 111, 248];
+/// First 8 bytes of `sha256("event:RivalryStandings")`
+pub const RIVALRY_STANDINGS_DISCRIMINATOR: [u8; 8] = [63, 12, 183, 191, 248, 88, 207, 231];
+/// First 8 bytes of `sha256("event:FeesClaimed")`
+pub const FEES_CLAIMED_DISCRIMINATOR: [u8; 8] = [22, 104, 110, 222, 38, 157, 14, 62];
+/// First 8 bytes of `sha256("event:ListingChanged")`
+pub const LISTING_CHANGED_DISCRIMINATOR: [u8; 8] = [250, 3, 123, 146, 103, 105, 212, 176];
+/// First 8 bytes of `sha256("event:DonationLogged")`
+pub const DONATION_LOGGED_DISCRIMINATOR: [u8; 8] = [36, 55, 204, 21, 88, 44, 18, 243];
+/// First 8 bytes of `sha256("event:DeadlineExtended")`
+pub const DEADLINE_EXTENDED_DISCRIMINATOR: [u8; 8] = [100, 53, 50, 91, 205, 71, 0, 103];
+/// First 8 bytes of `sha256("event:CampaignDerived")`
+pub const CAMPAIGN_DERIVED_DISCRIMINATOR: [u8; 8] = [163, 78, 111, 40, 111, 216, 240, 177];
+/// First 8 bytes of `sha256("event:HardGoalReached")`
+pub const HARD_GOAL_REACHED_DISCRIMINATOR: [u8; 8] = [206, 119, 44, 22, 218, 204, 198, 57];
+/// First 8 bytes of `sha256("event:DonationsSynced")`
+pub const DONATIONS_SYNCED_DISCRIMINATOR: [u8; 8] = [49, 221, 135, 64, 15, 238, 248, 76];
+/// First 8 bytes of `sha256("event:DonationQuarantined")`
+pub const DONATION_QUARANTINED_DISCRIMINATOR: [u8; 8] = [225, 146, 254, 37, 135, 5, 149, 106];
+/// First 8 bytes of `sha256("event:QuarantineReleased")`
+pub const QUARANTINE_RELEASED_DISCRIMINATOR: [u8; 8] = [255, 60, 16, 214, 51, 164, 119, 119];
+/// First 8 bytes of `sha256("event:DonationsMerged")`
+pub const DONATIONS_MERGED_DISCRIMINATOR: [u8; 8] = [209, 16, 212, 138, 80, 247, 51, 67];
+/// First 8 bytes of `sha256("event:FeeExemptionChanged")`
+pub const FEE_EXEMPTION_CHANGED_DISCRIMINATOR: [u8; 8] = [11, 106, 61, 75, 38, 87, 79, 60];
+/// First 8 bytes of `sha256("event:PreviewResult")`
+pub const PREVIEW_RESULT_DISCRIMINATOR: [u8; 8] = [222, 142, 30, 192, 72, 98, 237, 178];
+/// First 8 bytes of `sha256("event:MatchReclaimed")`
+pub const MATCH_RECLAIMED_DISCRIMINATOR: [u8; 8] = [127, 124, 103, 211, 248, 159, 32, 206];
+/// First 8 bytes of `sha256("event:WaitlistPromoted")`
+pub const WAITLIST_PROMOTED_DISCRIMINATOR: [u8; 8] = [201, 112, 255, 243, 3, 155, 226, 237];
+/// First 8 bytes of `sha256("event:CampaignPauseChanged")`
+pub const CAMPAIGN_PAUSE_CHANGED_DISCRIMINATOR: [u8; 8] = [78, 182, 121, 117, 37, 250, 205, 111];
+/// First 8 bytes of `sha256("event:CampaignCreated")`
+pub const CAMPAIGN_CREATED_DISCRIMINATOR: [u8; 8] = [9, 98, 69, 61, 53, 131, 64, 152];
+/// First 8 bytes of `sha256("event:FundsWithdrawn")`
+pub const FUNDS_WITHDRAWN_DISCRIMINATOR: [u8; 8] = [56, 130, 230, 154, 35, 92, 11, 118];
+/// First 8 bytes of `sha256("event:PlatformFeeChanged")`
+pub const PLATFORM_FEE_CHANGED_DISCRIMINATOR: [u8; 8] = [184, 2, 11, 62, 45, 242, 167, 236];
+/// First 8 bytes of `sha256("event:AdminTransferProposed")`
+pub const ADMIN_TRANSFER_PROPOSED_DISCRIMINATOR: [u8; 8] = [203, 168, 175, 51, 239, 104, 20, 85];
+/// First 8 bytes of `sha256("event:AdminTransferred")`
+pub const ADMIN_TRANSFERRED_DISCRIMINATOR: [u8; 8] = [255, 147, 182, 5, 199, 217, 38, 179];
+
+/// Current layout version of campaign accounts
+pub const CAMPAIGN_VERSION: u8 = 1;
+/// Maximum name length in bytes
+pub const MAX_NAME_LEN: usize = 100;
+/// Maximum description length in bytes
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+/// Maximum currency symbol length in bytes
+pub const MAX_CURRENCY_SYMBOL_LEN: usize = 8;
+/// Currency symbol used when a campaign doesn't set one
+pub const DEFAULT_CURRENCY_SYMBOL: &str = "SOL";
+/// Maximum donation message length in bytes
+pub const MAX_MESSAGE_LEN: usize = 280;
+/// Maximum thank-you message length in bytes
+pub const MAX_THANK_YOU_LEN: usize = 280;
+/// Byte `renounce_admin` must be given to confirm the irreversible renouncement
+pub const RENOUNCE_CONFIRMATION: u8 = 0x52;
+/// Number of entries an audit log holds before it wraps around
+pub const AUDIT_LOG_CAPACITY: usize = 64;
+
+/// Byte offsets of campaign fields, for `memcmp` filters. Every field has a fixed
+/// size, so these stay valid as new fields are appended to the account.
+pub const CAMPAIGN_VERSION_OFFSET: usize = DISCRIMINATOR_LEN;
+pub const CAMPAIGN_NAME_OFFSET: usize = CAMPAIGN_VERSION_OFFSET + 1;
+pub const CAMPAIGN_NAME_LEN_OFFSET: usize = CAMPAIGN_NAME_OFFSET + MAX_NAME_LEN;
+pub const CAMPAIGN_DESCRIPTION_OFFSET: usize = CAMPAIGN_NAME_LEN_OFFSET + 2;
+pub const CAMPAIGN_DESCRIPTION_LEN_OFFSET: usize = CAMPAIGN_DESCRIPTION_OFFSET + MAX_DESCRIPTION_LEN;
+pub const CAMPAIGN_AMOUNT_DONATED_OFFSET: usize = CAMPAIGN_DESCRIPTION_LEN_OFFSET + 2;
+pub const CAMPAIGN_ADMIN_OFFSET: usize = CAMPAIGN_AMOUNT_DONATED_OFFSET + 8;
+pub const CAMPAIGN_MIN_SECONDS_BETWEEN_DONATIONS_OFFSET: usize = CAMPAIGN_ADMIN_OFFSET + 32;
+pub const CAMPAIGN_DONATION_COUNT_OFFSET: usize = CAMPAIGN_MIN_SECONDS_BETWEEN_DONATIONS_OFFSET + 4;
+pub const CAMPAIGN_IDEMPOTENCY_KEY_OFFSET: usize = CAMPAIGN_DONATION_COUNT_OFFSET + 8;
+
+/// Byte offsets of the fixed-layout donor record fields, for `memcmp` filters
+pub const DONOR_RECORD_CAMPAIGN_OFFSET: usize = DISCRIMINATOR_LEN;
+pub const DONOR_RECORD_DONOR_OFFSET: usize = DONOR_RECORD_CAMPAIGN_OFFSET + 32;
+pub const DONOR_RECORD_AMOUNT_DONATED_OFFSET: usize = DONOR_RECORD_DONOR_OFFSET + 32;
+pub const DONOR_RECORD_DONATION_COUNT_OFFSET: usize = DONOR_RECORD_AMOUNT_DONATED_OFFSET + 8;
+pub const DONOR_RECORD_LAST_DONATED_AT_OFFSET: usize = DONOR_RECORD_DONATION_COUNT_OFFSET + 8;
+pub const DONOR_RECORD_BUMP_OFFSET: usize = DONOR_RECORD_LAST_DONATED_AT_OFFSET + 8;
+pub const DONOR_RECORD_DONOR_HASH_OFFSET: usize = DONOR_RECORD_BUMP_OFFSET + 1;
+pub const DONOR_RECORD_HIDE_AMOUNT_OFFSET: usize = DONOR_RECORD_DONOR_HASH_OFFSET + 32;
+
+/// Byte offsets of the fixed-layout organization donation fields, for `memcmp` filters
+pub const ORG_DONATION_CAMPAIGN_OFFSET: usize = DISCRIMINATOR_LEN;
+pub const ORG_DONATION_ORG_OFFSET: usize = ORG_DONATION_CAMPAIGN_OFFSET + 32;
+pub const ORG_DONATION_AMOUNT_DONATED_OFFSET: usize = ORG_DONATION_ORG_OFFSET + 32;
+pub const DONOR_RECORD_WEIGHTED_SCORE_OFFSET: usize = DONOR_RECORD_HIDE_AMOUNT_OFFSET + 1;
@@ -0,0 +1,352 @@
+//! Program error codes.
+
+/// Passes the program's error variants, each with its `#[msg(...)]`, to `$callback`
+#[macro_export]
+macro_rules! campaign_errors {
+    ($callback:ident) => {
+        $callback! {
+            #[msg("You are not authorized to perform this action.")]
+            Unauthorized, // Returned when a non-admin tries to withdraw
+
+            #[msg("Not enough funds in the campaign account.")]
+            InsufficientFunds, // Returned when withdrawal amount exceeds available balance
+
+            #[msg("Donations from this wallet are arriving too quickly.")]
+            RateLimited, // Returned when a donor donates again before the campaign's cooldown elapses
+
+            #[msg("A campaign with this idempotency key already exists.")]
+            AlreadyCreated, // Returned when a create is retried with the same idempotency key
+
+            #[msg("Source and destination campaigns must be different.")]
+            SameCampaign, // Returned when a campaign tries to donate to itself
+
+            #[msg("Fee must not exceed 10000 basis points.")]
+            InvalidFeeBps, // Returned when a campaign is created with a fee above 100%
+
+            #[msg("Fee recipient weights must add up to 10000 basis points.")]
+            InvalidFeeSplit, // Returned when fee recipient weights don't cover the whole fee
+
+            #[msg("Fee recipient account does not match the campaign's configuration.")]
+            FeeRecipientMismatch, // Returned when a fee recipient is missing or wrong in donate
+
+            #[msg("Name must be at most 100 bytes.")]
+            NameTooLong, // Returned when a campaign name exceeds its fixed-size field
+
+            #[msg("Description must be at most 500 bytes.")]
+            DescriptionTooLong, // Returned when a description exceeds its fixed-size field
+
+            #[msg("Campaign account is not in the legacy layout.")]
+            AlreadyMigrated, // Returned when migrating an account that already uses the current layout
+
+            #[msg("Deadline must be in the future.")]
+            InvalidDeadline, // Returned when a campaign is created with a past deadline
+
+            #[msg("Refund delay must not be negative.")]
+            InvalidRefundDelay, // Returned when a campaign is created with a negative refund delay
+
+            #[msg("Refunds are not open yet.")]
+            RefundNotYetOpen, // Returned when refund is called before deadline + refund_delay
+
+            #[msg("This campaign is not eligible for refunds.")]
+            RefundNotAvailable, // Returned when refund is called on a campaign without a deadline or that reached its goal
+
+            #[msg("Campaign is not active.")]
+            CampaignNotActive, // Returned when an instruction requires an active campaign
+
+            #[msg("Campaign is not cancelled.")]
+            CampaignNotCancelled, // Returned when reopening a campaign that isn't cancelled
+
+            #[msg("Reopen grace period must not be negative.")]
+            InvalidReopenGracePeriod, // Returned when a campaign is created with a negative reopen grace period
+
+            #[msg("The grace period for reopening this campaign has ended.")]
+            ReopenWindowClosed, // Returned when reopening after reopen_until
+
+            #[msg("A campaign that has paid refunds can never be reopened.")]
+            ReopenAfterRefund, // Returned when reopening a campaign with refunded_amount > 0
+
+            #[msg("Refund policy can no longer be changed.")]
+            RefundPolicyLocked, // Returned when changing the refund policy after the first donation
+
+            #[msg("Too many campaigns in one batch.")]
+            BatchTooLarge, // Returned when create_many exceeds MAX_CREATE_BATCH
+
+            #[msg("Batch accounts do not match the batch parameters.")]
+            BatchMismatch, // Returned when batch lengths differ or an account isn't the expected PDA
+
+            #[msg("Too many allowed destinations.")]
+            TooManyDestinations, // Returned when a campaign is created with more than 3 allowed destinations
+
+            #[msg("Funds cannot be sent to this destination.")]
+            DestinationNotAllowed, // Returned when a withdrawal recipient is not in the campaign's allowlist
+
+            #[msg("Extra space exceeds the maximum")]
+            InvalidExtraSpace, // Returned when `extra_space` exceeds `MAX_EXTRA_SPACE`
+
+            #[msg("Funds are reserved until the campaign meets its goal or its deadline passes")]
+            FundsReserved, // Returned when a campaign's funds are moved before it meets its target or passes its deadline (or, all-or-nothing, succeeds)
+
+            #[msg("The campaign's deadline has not passed yet")]
+            CampaignNotEnded, // Returned when finalizing a campaign before its deadline
+
+            #[msg("The organization account does not match the org argument")]
+            OrgMismatch, // Returned when `org` and the `org_donation` account are not provided together
+
+            #[msg("Arithmetic overflow")]
+            Overflow, // Returned when a lamport amount would overflow; display counters saturate instead
+
+            #[msg("Donors may still claim refunds from this campaign")]
+            RefundsOutstanding, // Returned when closing a campaign that still holds refundable donations
+
+            #[msg("Currency symbol is too long")]
+            SymbolTooLong, // Returned when the currency symbol exceeds `MAX_CURRENCY_SYMBOL_LEN` bytes
+
+            #[msg("Pledges need a positive amount and a future due date")]
+            InvalidPledge, // Returned when a pledge's amount is zero or `due_by` is not in the future
+
+            #[msg("The pledge is past its due date")]
+            PledgeExpired, // Returned when fulfilling a pledge after `due_by`
+
+            #[msg("The pledge is not past its due date yet")]
+            PledgeNotExpired, // Returned when expiring a pledge before `due_by`
+
+            #[msg("Crank bounty exceeds the maximum")]
+            InvalidCrankBounty, // Returned when `crank_bounty_bps` exceeds `MAX_CRANK_BOUNTY_BPS`
+
+            #[msg("The campaign's deadline has passed")]
+            CampaignEnded, // No longer returned (donations past the deadline get `CannotAcceptDonations`); kept so later codes don't shift
+
+            #[msg("This campaign only accepts donors with an attestation")]
+            AttestationRequired, // Returned when a gated campaign's donor passes no attestation
+
+            #[msg("The attestation is not valid for this donor and campaign")]
+            AttestationInvalid, // Returned when the attestation has the wrong issuer or subject, or has expired
+
+            #[msg("Attestation-gated campaigns need an issuer")]
+            InvalidAttestationIssuer, // Returned when `require_attestation` is set without an issuer
+
+            #[msg("The campaign is under dispute")]
+            CampaignDisputed, // Returned when moving a disputed campaign's funds anywhere but its dispute escrow
+
+            #[msg("The campaign is not under dispute")]
+            NotDisputed, // Returned when resolving a campaign without an open dispute
+
+            #[msg("Withdrawals from a disputed campaign need its dispute escrow")]
+            DisputeEscrowRequired, // Returned when withdrawing from a disputed campaign without passing the escrow
+
+            #[msg("The campaign has reached its maximum number of donors")]
+            DonorCapReached, // Returned when a new donor gives to a campaign at its `max_donors` cap
+
+            #[msg("The campaign admin differs from the one the donor expected")]
+            AdminChanged, // Returned when `expected_admin` doesn't match the campaign's admin
+
+            #[msg("A campaign's withdrawal cliff cannot be before its creation")]
+            InvalidWithdrawCliff, // Returned when a campaign is created with a non-zero `withdraw_cliff` in the past
+
+            #[msg("The campaign's withdrawal cliff has not been reached")]
+            CliffNotReached, // Returned when moving funds out of a campaign before its `withdraw_cliff`
+
+            #[msg("A campaign cannot be its own rival")]
+            SelfRivalry, // Returned when a campaign challenges itself
+
+            #[msg("The campaign has not challenged this one")]
+            RivalNotProposed, // Returned when accepting a challenge that wasn't proposed to the accepting campaign
+
+            #[msg("The campaigns are not rivals")]
+            NotRivals, // Returned when comparing campaigns that aren't linked as rivals
+
+            #[msg("The signer has no accrued fees to claim")]
+            NoFeesAccrued, // Returned when a wallet that isn't owed fees by the campaign claims them
+
+            #[msg("The campaign still holds unclaimed fees")]
+            FeesUnclaimed, // Returned when closing a campaign before its fee recipients have claimed their fees
+
+            #[msg("Message is too long")]
+            MessageTooLong, // Returned when a donation message exceeds `MAX_MESSAGE_LEN` bytes
+
+            #[msg("Only donors can post messages")]
+            NotADonor, // Returned when a wallet that never donated posts a message
+
+            #[msg("Batch is empty")]
+            EmptyBatch, // Returned when a batch instruction is given no items
+
+            #[msg("Hiding the total requires a deadline")]
+            HiddenTotalWithoutDeadline, // Returned when a campaign without a deadline is created with `hide_total_until_deadline`
+
+            #[msg("The campaign's total is hidden until its deadline")]
+            TotalHidden, // Returned when comparing rivals while either one's total is hidden
+
+            #[msg("The withdrawal nonce is stale")]
+            WithdrawReplay, // Returned when `withdraw_nonce` doesn't match the campaign's, e.g. a retried withdrawal that already went through
+
+            #[msg("The campaign account is below its rent-exempt minimum")]
+            AccountNotRentExempt, // Returned when paying out of a campaign whose balance has fallen below its rent floor
+
+            #[msg("The campaign has not been closed")]
+            CampaignNotClosed, // Returned when closing auxiliary accounts of a campaign that still exists
+
+            #[msg("Account is not an auxiliary account of this campaign")]
+            AuxAccountMismatch, // Returned when `close_aux` is given an account that isn't one of the campaign's auxiliary PDAs
+
+            #[msg("Renouncing the admin was not confirmed")]
+            RenounceNotConfirmed, // Returned when `renounce_admin` is called without `RENOUNCE_CONFIRMATION`
+
+            #[msg("A donor-count goal must be at least one donor")]
+            InvalidGoal, // Returned when a campaign is created with a `Donors` goal of 0
+
+            #[msg("Invalid anti-sniping window or extension")]
+            InvalidAntiSnipe, // Returned when the anti-sniping window or extension is negative, or a window is set without a deadline or extension
+
+            #[msg("The donor cannot give anything without dropping below rent exemption")]
+            WouldUnfundDonor, // Returned when `protect_donor_rent` leaves nothing of the donor's balance to donate
+
+            #[msg("The hard goal cannot be below the goal")]
+            InvalidHardGoal, // Returned when a campaign is created with a non-zero `hard_goal` below its `goal`
+
+            #[msg("The campaign holds no untracked deposits")]
+            NothingToSync, // Returned when `sync_donated` finds no lamports beyond what the bookkeeping accounts for
+
+            #[msg("A withdrawal condition needs both an account and its owning program")]
+            InvalidWithdrawCondition, // Returned when only one of `withdraw_condition` and `withdraw_condition_owner` is set
+
+            #[msg("The withdrawal condition is not met")]
+            ConditionNotMet, // Returned when withdrawing while the condition account is missing, not owned by the trusted program, or reads false
+
+            #[msg("Endowment share cannot exceed 100%")]
+            InvalidEndowmentBps, // Returned when a campaign is created with `endowment_bps` above 10000
+
+            #[msg("Funds in the endowment cannot be withdrawn")]
+            EndowmentProtected, // Returned when a withdrawal, or closing a successful campaign, would dip into the campaign's endowment
+
+            #[msg("A denylist entry account is required for this campaign")]
+            DenylistEntryRequired, // Returned when donating to a screened campaign without passing the donor's denylist entry address
+
+            #[msg("Donations from flagged wallets need a quarantine account")]
+            QuarantineRequired, // Returned when a flagged donor donates without passing their `QuarantinedDonation` PDA
+
+            #[msg("Quarantined donations are still pending release")]
+            QuarantinePending, // Returned when closing a campaign that still holds quarantined funds
+
+            #[msg("Donation amount is not a multiple of the campaign's donation step")]
+            InvalidDonationIncrement, // Returned when a donation or pledge isn't an exact multiple of `donation_step`
+
+            #[msg("Cannot merge a donor record into itself")]
+            MergeIntoSelf, // Returned when `merge_donations` is given the same wallet as old and new donor
+
+            #[msg("Deadline does not match the deadline mode")]
+            InvalidDeadlineMode, // Returned when a campaign sets the deadline field its mode doesn't use, or a slot deadline with a refund delay
+
+            #[msg("Rent sweep target cannot be the campaign itself")]
+            InvalidRentSweepTarget, // Returned when the rent sweep target is set to the campaign's own address
+
+            #[msg("The campaign has raised no more than its goal")]
+            NoOverage, // Returned when withdrawing overage from a campaign whose net raised, less prior withdrawals, doesn't exceed its amount goal
+
+            #[msg("Locale must be a two-letter lowercase ISO 639-1 code")]
+            InvalidLocale, // Returned when a campaign is created with a locale that isn't two lowercase ASCII letters
+
+            #[msg("Campaign cannot accept donations")]
+            CannotAcceptDonations, // Returned by every donation path when the campaign is cancelled, finalized or past its deadline; the reason is logged
+
+            #[msg("Match expiry must be in the future")]
+            InvalidMatchExpiry, // Returned when a campaign is created with a negative or past `match_expiry`
+
+            #[msg("The match pool belongs to another sponsor")]
+            MatchSponsorMismatch, // Returned when a second wallet tops up a pool whose funds haven't all gone back to its sponsor, or someone other than its sponsor reclaims it
+
+            #[msg("The match pool has not expired yet")]
+            MatchNotExpired, // Returned when reclaiming the pool before `match_expiry` (or the campaign's end)
+
+            #[msg("The match pool is empty")]
+            MatchPoolEmpty, // Returned when reclaiming a pool with nothing left in it (and, for failed or cancelled campaigns, no matched funds left to return)
+
+            #[msg("Weights must add up to 10000 and match the campaigns passed")]
+            InvalidWeights, // Returned when `donate_weighted` weights don't sum to 100%, exceed `MAX_WEIGHTED_CAMPAIGNS`, or don't pair up with the accounts
+
+            #[msg("Thank-you message must be at most 280 bytes")]
+            ThankYouTooLong, // Returned when a thank-you message exceeds its fixed-size field
+
+            #[msg("Withdrawal limit needs a positive period and at most 100%")]
+            InvalidWithdrawLimit, // Returned when a campaign is created with `max_withdraw_pct_per_period` above 10000, or set without a positive `withdraw_period`
+
+            #[msg("Withdrawal exceeds the limit for the current period")]
+            PeriodLimitExceeded, // Returned when moving funds out would take the period's total past `max_withdraw_pct_per_period` of `amount_donated`
+
+            #[msg("The campaign still has room for new donors")]
+            CampaignNotFull, // Returned when joining the waitlist of a campaign below its donor cap (or without one)
+
+            #[msg("Another wallet is ahead in the waitlist")]
+            NotNextInWaitlist, // Returned when promoting an entry that isn't at the head of the waitlist, or was already promoted
+
+            #[msg("No donor spot is free")]
+            NoFreeSpot, // Returned when promoting while the campaign is still at its donor cap
+
+            #[msg("Waitlist entry has not been promoted")]
+            NotPromoted, // Returned when donating with a waitlist entry that isn't holding a spot yet
+
+            #[msg("Campaign balance did not grow by the credited amount")]
+            InflowMismatch, // Returned by `strict-invariants` builds when a donation is credited without the lamports arriving
+
+            #[msg("Campaign is paused")]
+            CampaignPaused, // Returned when withdrawing from a paused campaign
+
+            #[msg("Campaign is not paused")]
+            CampaignNotPaused, // Returned when resuming a campaign that isn't paused
+
+            #[msg("Platform treasury account required")]
+            TreasuryRequired, // Returned when a withdrawal owes the platform fee but no treasury account was passed
+
+            #[msg("Account is not the platform treasury")]
+            InvalidTreasury, // Returned when the treasury account passed doesn't match the config's treasury
+
+            #[msg("Signer is not the pending admin")]
+            NotPendingAdmin, // Returned when accepting a campaign's admin role without being its proposed admin
+
+            #[msg("Wallet is flagged on the campaign's denylist")]
+            DonorFlagged, // Returned when a flagged wallet tries to pledge; its donations are quarantined instead
+
+            #[msg("The match pool must be reclaimed by its sponsor first")]
+            MatchPoolOutstanding, // Returned when closing a campaign whose match pool still holds sponsor lamports
+
+            #[msg("Untracked deposits cannot be credited to a campaign that refunds donors")]
+            SyncRefundable, // Returned when syncing a campaign with a refund policy, since an anonymous deposit has nobody to refund
+
+            #[msg("Merging would leave the campaign short of its success conditions")]
+            MergeBreaksGoal, // Returned when a merge would drop an all-or-nothing campaign that met its goal below its donor minimum
+        }
+    };
+}
+
+/// Defines `ErrorCode` from the variant list without Anchor's `#[error_code]`
+macro_rules! define_error_code {
+    ($(#[msg($msg:literal)] $variant:ident,)*) => {
+        /// Custom errors returned by the program
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum ErrorCode {
+            $($variant,)*
+        }
+
+        impl ErrorCode {
+            /// Every variant, in declaration order
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant,)*];
+
+            /// The numeric code the program returns for this error
+            pub const fn code(self) -> u32 {
+                $crate::constants::ERROR_CODE_OFFSET + self as u32
+            }
+
+            /// Looks up an error from the numeric code in a failed transaction
+            pub fn from_code(code: u32) -> Option<Self> {
+                Self::ALL.iter().copied().find(|error| error.code() == code)
+            }
+
+            /// The message logged by the program for this error
+            pub const fn message(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $msg,)*
+                }
+            }
+        }
+    };
+}
+pub(crate) use define_error_code;
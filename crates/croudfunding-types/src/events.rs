@@ -0,0 +1,349 @@
+//! Events emitted by the program.
+
+/// Expands the program's event structs with the given attributes
+#[macro_export]
+macro_rules! campaign_events {
+    ($(#[$($attr:tt)*])*) => {
+        $(#[$($attr)*])*
+        pub struct CampaignDonationSent {
+            pub source: Pubkey,         // Campaign the funds left
+            pub destination: Pubkey,    // Campaign the funds went to
+            pub amount: u64,            // Amount moved (in lamports)
+            pub timestamp: i64,         // Unix timestamp of the transfer
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignDonationReceived {
+            pub destination: Pubkey,    // Campaign the funds went to
+            pub source: Pubkey,         // Campaign recorded as the donor
+            pub amount: u64,            // Amount received (in lamports)
+            pub new_total: u64,         // Destination's amount_donated after the donation, 0 if its total is hidden
+            pub total_hidden: bool,     // Whether the destination hides its total until the deadline
+            pub timestamp: i64,         // Unix timestamp of the transfer
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignCancelled {
+            pub campaign: Pubkey,       // Campaign that was cancelled
+            pub cancelled_at: i64,      // Unix timestamp of the cancellation
+            pub reopen_until: i64,      // Last moment the admin may reopen it
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignReopened {
+            pub campaign: Pubkey,       // Campaign that was reopened
+            pub cancelled_at: i64,      // Unix timestamp of the cancellation being undone
+            pub timestamp: i64,         // Unix timestamp of the reopen
+        }
+
+        $(#[$($attr)*])*
+        pub struct DonationReceived {
+            pub campaign: Pubkey,       // Campaign that received the donation
+            pub donor_hash: [u8; 32],   // Pseudonymous donor identifier, stable within the campaign
+            pub amount: u64,            // Amount credited to the campaign (in lamports), 0 if hidden by the donor
+            pub amount_hidden: bool,    // Whether the donor chose to hide their amount
+            pub new_total: u64,         // Campaign's amount_donated after the donation, 0 if the total is hidden
+            pub verified_raised: u64,   // Campaign's verified_raised after the donation, 0 if the total is hidden
+            pub total_hidden: bool,     // Whether the campaign hides its total until the deadline
+            pub currency_symbol: String, // Symbol to display the amounts with
+            pub thank_you: String,      // Creator's thank-you for wallets to show the donor (empty = none)
+            pub timestamp: i64,         // Unix timestamp of the donation
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignFinalized {
+            pub campaign: Pubkey,       // Campaign that was finalized
+            pub status: CampaignStatus, // Successful or Failed
+            pub net_raised: u64,        // Amount raised net of refunds at finalization
+            pub currency_symbol: String, // Symbol to display the amounts with
+            pub timestamp: i64,         // Unix timestamp of the finalization
+        }
+
+        $(#[$($attr)*])*
+        pub struct DonationMatched {
+            pub campaign: Pubkey,       // Campaign the match was credited to
+            pub donor_hash: [u8; 32],   // Pseudonymous identifier of the matched donor
+            pub amount: u64,            // Amount actually matched (in lamports), at most the donation; 0 if amount_hidden
+            pub amount_hidden: bool,    // Whether the donor hides their amount (the match would give it away)
+            pub remaining_pool: u64,    // Campaign's match_pool after the match; 0 if amount_hidden
+            pub timestamp: i64,         // Unix timestamp of the match
+        }
+
+        $(#[$($attr)*])*
+        pub struct MatchPoolFunded {
+            pub campaign: Pubkey,       // Campaign whose pool was topped up
+            pub sponsor: Pubkey,        // Wallet that funded the pool
+            pub amount: u64,            // Amount added (in lamports)
+            pub match_pool: u64,        // Campaign's match_pool after the top-up
+            pub timestamp: i64,         // Unix timestamp of the top-up
+        }
+
+        $(#[$($attr)*])*
+        pub struct FundsMerged {
+            pub source: Pubkey,         // Campaign the balance left
+            pub destination: Pubkey,    // Campaign the balance was merged into
+            pub amount: u64,            // Amount moved (in lamports)
+            pub new_total: u64,         // Destination's amount_donated after the merge, 0 if its total is hidden
+            pub total_hidden: bool,     // Whether the destination hides its total until the deadline
+            pub timestamp: i64,         // Unix timestamp of the merge
+        }
+
+        $(#[$($attr)*])*
+        pub struct Reconciliation {
+            pub campaign: Pubkey,       // Campaign that was reconciled
+            pub balanced: bool,         // Whether the balance matches the bookkeeping exactly
+            pub expected_balance: u64,  // Lamports the bookkeeping accounts for
+            pub actual_balance: u64,    // Lamports the account actually holds
+            pub drift: i64,             // actual_balance - expected_balance (positive = unrecorded deposits)
+            pub timestamp: i64,         // Unix timestamp of the reconciliation
+        }
+
+        $(#[$($attr)*])*
+        pub struct DisputeRaised {
+            pub campaign: Pubkey,       // Campaign put under dispute
+            pub timestamp: i64,         // Unix timestamp the dispute was raised
+        }
+
+        $(#[$($attr)*])*
+        pub struct DisputeResolved {
+            pub campaign: Pubkey,       // Campaign whose dispute was resolved
+            pub amount: u64,            // Escrowed lamports released (in lamports)
+            pub to_admin: bool,         // True if released to the admin, false if returned for refunds
+            pub timestamp: i64,         // Unix timestamp of the resolution
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignConfig {
+            pub campaign: Pubkey,       // Campaign the settings belong to
+            pub admin: Pubkey,          // Current admin
+            pub fee_bps: u16,           // Platform fee taken from each donation, in basis points
+            pub fee_recipients: [FeeRecipient; 2], // Wallets sharing the fee and their weights
+            pub goal: u64,              // Funding goal in lamports or donors (0 = no goal)
+            pub goal_type: GoalType,    // Whether goal counts lamports or donors
+            pub hard_goal: u64,         // Stretch goal in the same unit (0 = none)
+            pub deadline: i64,          // Unix timestamp the campaign ends at (0 = no deadline)
+            pub min_seconds_between_donations: u32, // Minimum delay between two donations from the same wallet
+            pub refund_policy: RefundPolicy, // Refunds offered to donors
+            pub refund_delay: i64,      // Seconds after the deadline before refunds of a failed campaign open
+            pub reopen_grace_period: i64, // Seconds after a cancellation during which the admin may reopen
+            pub allowed_destinations: Vec<Pubkey>, // Addresses funds may be withdrawn to (empty = any)
+            pub verification_min: u64,  // Minimum first donation counting toward verified_raised
+            pub currency_symbol: String, // Symbol clients display amounts with
+            pub crank_bounty_bps: u16,  // Share of each cranked refund paid to the cranker, in basis points
+            pub min_donors: u32,        // Distinct donors required for the campaign to succeed
+            pub max_donors: u32,        // Cap on distinct donors (0 = unlimited)
+            pub require_attestation: bool, // Whether donors need an attestation
+            pub attestation_issuer: Pubkey, // Issuer trusted to attest donors
+            pub dust_threshold: u64,    // Refundable balance swept to the admin on close
+            pub withdraw_cliff: i64,    // Unix timestamp before which no funds leave the campaign (0 = no cliff)
+            pub accrue_fees: bool,      // Whether fees are held for recipients to claim
+            pub hide_total_until_deadline: bool, // Whether the running total is hidden until the deadline
+            pub listed: bool,           // Whether the campaign appears in public listings
+            pub event_level: EventLevel, // Which event each donation emits
+            pub donation_maturity_slots: u64, // Slots a donation stays pending before the admin can spend it
+            pub anti_snipe_window: i64, // Seconds before the deadline in which a donation extends it
+            pub anti_snipe_extension: i64, // Seconds such a donation adds to the deadline
+            pub withdraw_condition: Pubkey, // Flag account gating withdrawals (default = none)
+            pub withdraw_condition_owner: Pubkey, // Program trusted to own the flag account
+            pub endowment_bps: u16,     // Share of each donation kept in the endowment, in basis points
+            pub denylist: Pubkey,       // Authority whose denylist donors are screened against (default = none)
+            pub donation_step: u64,     // Unit donations must be a multiple of (0 = any amount)
+            pub fee_exempt: bool,       // Whether the config authority waived the platform fee
+            pub deadline_mode: DeadlineMode, // Whether the deadline is a timestamp or a slot
+            pub deadline_slot: u64,     // Slot the campaign ends at in Slot mode (0 = no slot deadline)
+            pub rent_sweep_target: Pubkey, // Wallet reclaimed rent of auxiliary accounts goes to
+            pub locale: [u8; 2],        // ISO 639-1 language code ([0; 2] = unspecified)
+            pub match_expiry: i64,      // Unix timestamp the sponsor may reclaim the unused pool after (0 = at the deadline)
+            pub max_withdraw_pct_per_period: u16, // Share of amount_donated movable out per period, in basis points (0 = no cap)
+            pub withdraw_period: i64,   // Length of a withdrawal period in seconds
+        }
+
+        $(#[$($attr)*])*
+        pub struct RivalryLinked {
+            pub campaign: Pubkey,       // Campaign that issued the challenge
+            pub rival: Pubkey,          // Campaign that accepted it
+            pub campaign_raised: u64,   // Challenger's net raised at the time of the link, 0 if hidden
+            pub rival_raised: u64,      // Rival's net raised at the time of the link, 0 if hidden
+            pub timestamp: i64,         // Unix timestamp of the acceptance
+        }
+
+        $(#[$($attr)*])*
+        pub struct RivalryStandings {
+            pub campaign: Pubkey,       // Campaign the standings were requested for
+            pub rival: Pubkey,          // Its linked rival
+            pub campaign_raised: u64,   // Campaign's net raised
+            pub rival_raised: u64,      // Rival's net raised
+            pub leader: Pubkey,         // Campaign that has raised more (default on a tie)
+            pub timestamp: i64,         // Unix timestamp of the comparison
+        }
+
+        $(#[$($attr)*])*
+        pub struct FeesClaimed {
+            pub campaign: Pubkey,       // Campaign the fees were held in
+            pub recipient: Pubkey,      // Fee recipient that claimed
+            pub amount: u64,            // Fees paid out (in lamports)
+            pub timestamp: i64,         // Unix timestamp of the claim
+        }
+
+        $(#[$($attr)*])*
+        pub struct ListingChanged {
+            pub campaign: Pubkey,       // Campaign whose listing changed
+            pub listed: bool,           // Whether it now appears in public listings
+            pub locale: [u8; 2],        // Campaign's ISO 639-1 language code, for filtering listings ([0; 2] = unspecified)
+            pub timestamp: i64,         // Unix timestamp of the change
+        }
+
+        $(#[$($attr)*])*
+        pub struct DonationLogged {
+            pub campaign: Pubkey,       // Campaign that received the donation
+            pub amount: u64,            // Amount credited to the campaign (in lamports), 0 if hidden by the donor
+            pub timestamp: i64,         // Unix timestamp of the donation
+        }
+
+        $(#[$($attr)*])*
+        pub struct DeadlineExtended {
+            pub campaign: Pubkey,       // Campaign whose deadline moved
+            pub previous_deadline: i64, // Deadline before the late donation
+            pub deadline: i64,          // New deadline
+            pub timestamp: i64,         // Unix timestamp of the donation that triggered it
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignDerived {
+            pub creator: Pubkey,        // Creator the address was derived for
+            pub idempotency_key: [u8; 16], // Client key the address was derived for
+            pub campaign: Pubkey,       // Campaign PDA for these seeds
+            pub bump: u8,               // Its bump seed
+        }
+
+        $(#[$($attr)*])*
+        pub struct HardGoalReached {
+            pub campaign: Pubkey,       // Campaign that reached its stretch goal
+            pub hard_goal: u64,         // The stretch goal, in lamports or donors
+            pub progress: u64,          // Campaign's progress after the donation that crossed it
+            pub timestamp: i64,         // Unix timestamp of that donation
+        }
+
+        $(#[$($attr)*])*
+        pub struct DonationsSynced {
+            pub campaign: Pubkey,       // Campaign whose untracked deposits were credited
+            pub amount: u64,            // Lamports credited to amount_donated
+            pub new_total: u64,         // Campaign's amount_donated after the sync (0 while total_hidden)
+            pub total_hidden: bool,     // Whether the campaign hides its running total
+            pub timestamp: i64,         // Unix timestamp of the sync
+        }
+
+        $(#[$($attr)*])*
+        pub struct DonationQuarantined {
+            pub campaign: Pubkey,       // Campaign holding the funds
+            pub donor: Pubkey,          // Flagged wallet
+            pub amount: u64,            // Lamports quarantined by this donation
+            pub total_quarantined: u64, // Lamports now held for the wallet
+            pub timestamp: i64,         // Unix timestamp of the donation
+        }
+
+        $(#[$($attr)*])*
+        pub struct QuarantineReleased {
+            pub campaign: Pubkey,       // Campaign that held the funds
+            pub donor: Pubkey,          // Flagged wallet
+            pub amount: u64,            // Lamports released
+            pub accepted: bool,         // true if credited to the campaign, false if refunded to the donor
+            pub timestamp: i64,         // Unix timestamp of the release
+        }
+
+        $(#[$($attr)*])*
+        pub struct DonationsMerged {
+            pub campaign: Pubkey,       // Campaign the records belong to
+            pub old_donor_hash: [u8; 32], // Pseudonym of the wallet whose record was closed
+            pub new_donor_hash: [u8; 32], // Pseudonym of the wallet now holding the history
+            pub amount: u64,            // Lamports moved over from the old record
+            pub donor_total: u64,       // New record's cumulative contribution after the merge
+            pub timestamp: i64,         // Unix timestamp of the merge
+        }
+
+        $(#[$($attr)*])*
+        pub struct FeeExemptionChanged {
+            pub campaign: Pubkey,       // Campaign whose exemption changed
+            pub fee_exempt: bool,       // Whether donations now skip the platform fee
+            pub timestamp: i64,         // Unix timestamp of the change
+        }
+
+        $(#[$($attr)*])*
+        pub struct PreviewResult {
+            pub campaign: Pubkey,       // Campaign the donation was previewed against
+            pub amount: u64,            // Gross amount previewed (in lamports)
+            pub fee: u64,               // Platform fee that would be taken
+            pub net_to_campaign: u64,   // Amount that would be credited after the fee
+            pub matched: u64,           // Amount the match pool would add on top
+            pub new_total: u64,         // Campaign's amount_donated after the donation, 0 if the total is hidden
+            pub crosses_goal: bool,     // Whether the donation would make the campaign meet its goal, false if the total is hidden
+        }
+
+        $(#[$($attr)*])*
+        pub struct MatchReclaimed {
+            pub campaign: Pubkey,       // Campaign whose pool was reclaimed
+            pub sponsor: Pubkey,        // Sponsor the unused funds went back to
+            pub amount: u64,            // Amount returned (in lamports), the unused pool plus `matched_returned`
+            pub matched_returned: u64,  // Part of the amount that had already matched donations (failed or cancelled campaigns)
+            pub timestamp: i64,         // Unix timestamp of the reclaim
+        }
+
+        $(#[$($attr)*])*
+        pub struct WaitlistPromoted {
+            pub campaign: Pubkey,       // Campaign a spot freed up on
+            pub donor: Pubkey,          // Wallet the spot is held for
+            pub position: u64,          // The wallet's place in line
+            pub timestamp: i64,         // Unix timestamp of the promotion
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignPauseChanged {
+            pub campaign: Pubkey,       // Campaign paused or resumed
+            pub paused: bool,           // Whether the campaign is now paused
+            pub timestamp: i64,         // Unix timestamp of the change
+        }
+
+        $(#[$($attr)*])*
+        pub struct CampaignCreated {
+            pub campaign: Pubkey,       // Campaign that was created
+            pub admin: Pubkey,          // Creator, who starts out as the admin
+            pub goal: u64,              // Goal, in lamports or donors depending on goal_type (0 = none)
+            pub deadline: i64,          // Unix timestamp deadline (0 = none or slot-based)
+            pub timestamp: i64,         // Unix timestamp of the creation
+        }
+
+        $(#[$($attr)*])*
+        pub struct FundsWithdrawn {
+            pub campaign: Pubkey,       // Campaign the funds left
+            pub recipient: Pubkey,      // Account credited (the dispute escrow while disputed)
+            pub amount: u64,            // Amount withdrawn (in lamports), including the platform fee
+            pub platform_fee: u64,      // Part of the amount paid to the platform treasury
+            pub total_withdrawn: u64,   // Campaign's amount_withdrawn after the withdrawal
+            pub escrowed: bool,         // Whether the funds are held in escrow pending a dispute
+            pub timestamp: i64,         // Unix timestamp of the withdrawal
+        }
+
+        $(#[$($attr)*])*
+        pub struct PlatformFeeChanged {
+            pub fee_bps: u16,           // New platform fee, in basis points
+            pub treasury: Pubkey,       // New fee treasury
+            pub timestamp: i64,         // Unix timestamp of the change
+        }
+
+        $(#[$($attr)*])*
+        pub struct AdminTransferProposed {
+            pub campaign: Pubkey,       // Campaign changing hands
+            pub admin: Pubkey,          // Current admin
+            pub pending_admin: Pubkey,  // Proposed admin (default = proposal withdrawn)
+            pub timestamp: i64,         // Unix timestamp of the proposal
+        }
+
+        $(#[$($attr)*])*
+        pub struct AdminTransferred {
+            pub campaign: Pubkey,       // Campaign that changed hands
+            pub previous_admin: Pubkey, // Admin before the transfer
+            pub admin: Pubkey,          // New admin
+            pub timestamp: i64,         // Unix timestamp of the acceptance
+        }
+    };
+}
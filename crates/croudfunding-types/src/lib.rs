@@ -0,0 +1,178 @@
+//! Account, event and error types of the croudfunding program.
+//!
+//! The definitions are written once, inside the macros exported by this crate.
+//! The on-chain program expands them with Anchor's attributes, and this crate
+//! expands them with plain borsh derives, so indexers and off-chain services can
+//! decode account data and event logs without depending on Anchor or
+//! `solana-program`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use errors::define_error_code;
+
+pub mod constants; // Seeds, discriminators and byte offsets
+mod errors; // Program error codes
+mod events; // Events emitted by the program
+mod state; // Account structs and the types they embed
+
+/// A 32-byte account address, borsh-compatible with `solana_program::pubkey::Pubkey`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    /// Returns the raw address bytes
+    pub const fn to_bytes(self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl From<[u8; 32]> for Pubkey {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// The seed a campaign's idempotency key contributes to its PDA: `[CAMPAIGN_SEED, creator, seed]`
+///
+/// The all-zero key (no key given) contributes no bytes, so such campaigns live at the
+/// original one-per-wallet address `[CAMPAIGN_SEED, creator]`, like every campaign created
+/// before idempotency keys existed.
+pub fn campaign_seed_key(idempotency_key: &[u8; 16]) -> &[u8] {
+    if *idempotency_key == [0; 16] {
+        &[]
+    } else {
+        idempotency_key
+    }
+}
+
+/// Reads a zero-padded UTF-8 field, returning `None` if `len` or the bytes are invalid
+pub fn padded_str(bytes: &[u8], len: u16) -> Option<&str> {
+    core::str::from_utf8(bytes.get(..usize::from(len))?).ok()
+}
+
+/// Writes `value` into a fixed-size field, zeroing the unused tail, and returns its length.
+/// Returns `None` (leaving the field untouched) if `value` doesn't fit.
+pub fn write_padded(field: &mut [u8], value: &str) -> Option<u16> {
+    let bytes = value.as_bytes();
+    if bytes.len() > field.len() {
+        return None;
+    }
+    let (head, tail) = field.split_at_mut(bytes.len());
+    head.copy_from_slice(bytes);
+    tail.fill(0);
+    u16::try_from(bytes.len()).ok()
+}
+
+campaign_types!(#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]);
+campaign_accounts!(#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]);
+audit_log_accounts!(
+    entry: #[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)],
+    log: #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+);
+campaign_events!(#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]);
+campaign_errors!(define_error_code);
+
+impl Campaign {
+    /// The campaign name, or `None` if the stored bytes are not valid UTF-8
+    pub fn name(&self) -> Option<&str> {
+        padded_str(&self.name, self.name_len)
+    }
+
+    /// The campaign description, or `None` if the stored bytes are not valid UTF-8
+    pub fn description(&self) -> Option<&str> {
+        padded_str(&self.description, self.description_len)
+    }
+
+    /// The creator's thank-you message, or `None` if the stored bytes are not valid UTF-8
+    pub fn thank_you(&self) -> Option<&str> {
+        padded_str(&self.thank_you, self.thank_you_len)
+    }
+
+    /// The currency symbol amounts are displayed with, or `None` if the stored bytes are not valid UTF-8
+    pub fn currency_symbol(&self) -> Option<&str> {
+        padded_str(&self.currency_symbol, self.currency_symbol_len)
+    }
+}
+
+impl CampaignArchive {
+    /// The archived campaign name, or `None` if the stored bytes are not valid UTF-8
+    pub fn name(&self) -> Option<&str> {
+        padded_str(&self.name, self.name_len)
+    }
+}
+
+impl DonationMessage {
+    /// The message text, or `None` if the stored bytes are not valid UTF-8
+    pub fn text(&self) -> Option<&str> {
+        padded_str(&self.text, self.text_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constants::*;
+
+    /// A campaign decoded from all-zero account data (without the discriminator)
+    fn zeroed_campaign() -> Campaign {
+        Campaign::deserialize(&mut &[0u8; 4096][..]).unwrap()
+    }
+
+    #[test]
+    fn padded_fields_round_trip() {
+        let mut field = [0xffu8; 8];
+        assert_eq!(write_padded(&mut field, "héllo"), Some(6));
+        assert_eq!(field, *b"h\xc3\xa9llo\0\0");
+        assert_eq!(padded_str(&field, 6), Some("héllo"));
+
+        // Exactly full, then too long (the field is left as it was)
+        assert_eq!(write_padded(&mut field, "12345678"), Some(8));
+        assert_eq!(write_padded(&mut field, "123456789"), None);
+        assert_eq!(padded_str(&field, 8), Some("12345678"));
+        assert_eq!(write_padded(&mut field, ""), Some(0));
+        assert_eq!(field, [0; 8]);
+    }
+
+    #[test]
+    fn padded_str_rejects_bad_lengths_and_utf8() {
+        assert_eq!(padded_str(b"abc", 4), None);
+        assert_eq!(padded_str(b"\xc3\xa9", 1), None);
+        assert_eq!(padded_str(b"abc", 0), Some(""));
+    }
+
+    #[test]
+    fn only_a_real_idempotency_key_changes_the_seed() {
+        assert!(campaign_seed_key(&[0; 16]).is_empty());
+        let key = [7; 16];
+        assert_eq!(campaign_seed_key(&key), &key[..]);
+    }
+
+    #[test]
+    fn campaign_round_trips_at_the_published_offsets() {
+        let mut campaign = zeroed_campaign();
+        campaign.name_len = write_padded(&mut campaign.name, "Wells").unwrap();
+        campaign.description_len = write_padded(&mut campaign.description, "Clean water").unwrap();
+        campaign.amount_donated = 0x0102_0304_0506_0708;
+        campaign.admin = Pubkey([9; 32]);
+        campaign.min_seconds_between_donations = 60;
+        campaign.donation_count = 42;
+        campaign.idempotency_key = [3; 16];
+        campaign.status = CampaignStatus::Failed;
+
+        let mut data = CAMPAIGN_DISCRIMINATOR.to_vec();
+        campaign.serialize(&mut data).unwrap();
+        let field = |offset: usize, len: usize| &data[offset..offset + len];
+        assert_eq!(field(CAMPAIGN_NAME_OFFSET, 5), b"Wells");
+        assert_eq!(field(CAMPAIGN_NAME_LEN_OFFSET, 2), 5u16.to_le_bytes());
+        assert_eq!(field(CAMPAIGN_DESCRIPTION_OFFSET, 11), b"Clean water");
+        assert_eq!(field(CAMPAIGN_AMOUNT_DONATED_OFFSET, 8), campaign.amount_donated.to_le_bytes());
+        assert_eq!(field(CAMPAIGN_ADMIN_OFFSET, 32), [9; 32]);
+        assert_eq!(field(CAMPAIGN_MIN_SECONDS_BETWEEN_DONATIONS_OFFSET, 4), 60u32.to_le_bytes());
+        assert_eq!(field(CAMPAIGN_DONATION_COUNT_OFFSET, 8), 42u64.to_le_bytes());
+        assert_eq!(field(CAMPAIGN_IDEMPOTENCY_KEY_OFFSET, 16), [3; 16]);
+
+        let decoded = Campaign::try_from_slice(&data[DISCRIMINATOR_LEN..]).unwrap();
+        assert_eq!(decoded, campaign);
+        assert_eq!(decoded.name(), Some("Wells"));
+        assert_eq!(decoded.description(), Some("Clean water"));
+    }
+}
@@ -0,0 +1,379 @@
+//! Account structs and the plain types they embed.
+
+/// Expands the plain (non-account) types stored inside accounts with the given attributes
+#[macro_export]
+macro_rules! campaign_types {
+    ($(#[$($attr:tt)*])*) => {
+        /// A wallet receiving part of a campaign's fee
+        $(#[$($attr)*])*
+        pub struct FeeRecipient {
+            pub recipient: Pubkey,      // Wallet receiving the share
+            pub weight_bps: u16,        // Share of the fee (in basis points)
+        }
+
+        /// Borsh-encoded return data of `donate`, visible in simulations and to CPI callers
+        $(#[$($attr)*])*
+        pub struct DonateResult {
+            pub net_amount: u64,        // Amount credited to the campaign after fees (in lamports)
+            pub new_total: u64,         // Campaign's amount_donated after the donation, 0 if the total is hidden
+            pub donor_total: u64,       // Donor's cumulative contribution after the donation
+            pub receipt_number: u64,    // Campaign's donation_count after the donation
+            pub goal_reached: bool,     // Whether the campaign has a goal and its progress meets it, false if the total is hidden
+            pub total_hidden: bool,     // Whether the campaign hides its total until the deadline
+        }
+
+        /// Lifecycle state of a campaign
+        $(#[$($attr)*])*
+        pub enum CampaignStatus {
+            #[default]
+            Active,                     // Accepting donations
+            Cancelled,                  // Cancelled by the admin; donors can claim refunds
+            Successful,                 // Finalized after the deadline with its goal met; funds are released
+            Failed,                     // Finalized after the deadline with its goal missed
+            Paused,                     // Paused by the admin; donations and withdrawals wait for `resume_campaign`
+        }
+
+        /// Which refunds a campaign offers its donors, fixed once the first donation arrives
+        $(#[$($attr)*])*
+        pub enum RefundPolicy {
+            None,                       // Keep-it-all: no refunds, ever
+            #[default]
+            OnFailure,                  // Refunds only if the campaign is cancelled or misses its goal
+            UntilDeadline,              // Donors may take their donation back any time before the deadline
+        }
+
+        /// What a campaign's `goal` counts
+        $(#[$($attr)*])*
+        pub enum GoalType {
+            #[default]
+            Amount,                     // Net lamports raised
+            Donors,                     // Distinct donors
+        }
+
+        /// How much `donate` logs, so micro-donation-heavy campaigns can cut log costs
+        $(#[$($attr)*])*
+        pub enum EventLevel {
+            #[default]
+            Full,                       // The complete `DonationReceived` event
+            Minimal,                    // A `DonationLogged` event with just the campaign and amount
+            None,                       // No donation events; withdrawals and admin actions still emit
+        }
+
+        /// Which clock a campaign's deadline is measured against
+        $(#[$($attr)*])*
+        pub enum DeadlineMode {
+            #[default]
+            Timestamp,                  // `deadline`, a Unix timestamp
+            Slot,                       // `deadline_slot`, for deterministic timing
+        }
+    };
+}
+
+/// Expands the program's account structs with the given attributes
+#[macro_export]
+macro_rules! campaign_accounts {
+    ($(#[$($attr:tt)*])*) => {
+        $(#[$($attr)*])*
+        pub struct Campaign {
+            pub version: u8,            // Account layout version (see CAMPAIGN_VERSION)
+            pub name: [u8; 100],        // Campaign name, UTF-8, zero-padded
+            pub name_len: u16,          // Number of bytes of `name` in use
+            pub description: [u8; 500], // Campaign description, UTF-8, zero-padded
+            pub description_len: u16,   // Number of bytes of `description` in use
+            pub amount_donated: u64,    // Total amount donated (in lamports)
+            pub admin: Pubkey,          // Admin (creator) of the campaign
+            pub min_seconds_between_donations: u32, // Per-donor cooldown between donations (0 = disabled)
+            pub donation_count: u64,    // Number of successful donations received (saturating)
+            pub idempotency_key: [u8; 16], // Client key used in the PDA seeds at creation (all zero = none)
+            pub fee_bps: u16,           // Fee taken from each donation (in basis points)
+            pub fee_recipients: [FeeRecipient; 2], // Wallets sharing the fee and their weights
+            pub rent_minimum: u64,      // Rent-exempt minimum for the account's current size
+            pub follower_count: u32,    // Number of wallets following the campaign (saturating)
+            pub goal: u64,              // Soft funding goal deciding success (in lamports or donors, see goal_type; 0 = no goal)
+            pub deadline: i64,          // Unix timestamp donations close at (0 = no deadline)
+            pub created_at: i64,        // Unix timestamp of creation
+            pub refund_delay: i64,      // Seconds after the deadline before refunds open
+            pub refunded_amount: u64,   // Total refunded to donors (in lamports)
+            pub status: CampaignStatus, // Lifecycle state
+            pub reopen_grace_period: i64, // Seconds after cancellation during which the admin may reopen
+            pub cancelled_at: i64,      // Unix timestamp of the last cancellation (0 = never cancelled)
+            pub reopen_until: i64,      // Last moment the admin may reopen, fixed at cancel time
+            pub donor_salt: [u8; 32],   // Per-campaign salt for pseudonymous donor hashes
+            pub refund_policy: RefundPolicy, // Refunds offered to donors
+            pub allowed_destinations: [Pubkey; 3], // Only addresses funds may be withdrawn to; set at creation, never modified
+            pub allowed_destination_count: u8, // Entries of allowed_destinations in use (0 = any destination)
+            pub verification_min: u64,   // Minimum first donation counted toward verified_raised (in lamports)
+            pub verified_raised: u64,    // Sum of first donations of at least verification_min from distinct wallets
+            pub allocated_space: u32,    // Bytes allocated to the account, at least Campaign::LEN; the rest is headroom for growth
+            pub match_pool: u64,         // Sponsor lamports held in the account, still available to match donations
+            pub matched_total: u64,      // Lamports matched and not returned to the sponsor; matched_total + match_pool is everything the sponsor has in the campaign
+            pub donor_count: u32,        // Wallets (or campaigns) currently holding a donor record (saturating)
+            pub bump: u8,                // PDA bump seed of the campaign address
+            pub currency_symbol: [u8; 8], // Symbol clients display amounts with (UTF-8, zero-padded)
+            pub currency_symbol_len: u16, // Bytes of currency_symbol in use
+            pub amount_withdrawn: u64,   // Lamports moved out by the admin (withdrawals and transfers to other campaigns)
+            pub pledged_total: u64,     // Lamports promised by open pledges, not yet funded
+            pub crank_bounty_bps: u16,   // Share of each cranked refund paid to the cranker (in basis points)
+            pub min_donors: u32,         // Distinct donors required, on top of the goal, for the campaign to succeed
+            pub require_attestation: bool, // Whether donors must hold an attestation from attestation_issuer
+            pub attestation_issuer: Pubkey, // Issuer whose attestations admit donors when require_attestation is set
+            pub disputed: bool,          // Set by the config authority; withdrawals go to the dispute escrow while set
+            pub max_donors: u32,         // Cap on distinct donors; once reached only existing donors can give (0 = unlimited)
+            pub dust_threshold: u64,     // Refundable balance at or below which closing sweeps it to the admin
+            pub withdraw_cliff: i64,     // Unix timestamp before which the admin can't move funds out (0 = no cliff)
+            pub rival: Pubkey,           // Campaign this one is in a head-to-head challenge with (default = none)
+            pub proposed_rival: Pubkey,  // Campaign challenged by the admin, awaiting its admin's acceptance (default = none)
+            pub accrue_fees: bool,       // Fees are held in the campaign for recipients to claim instead of paid out per donation
+            pub fees_accrued: [u64; 2],  // Unclaimed fees held for each fee recipient, in the same order
+            pub message_count: u64,      // Donation messages posted; the next message's sequence number
+            pub hide_total_until_deadline: bool, // Keep the running total out of events and return data until the deadline
+            pub withdraw_nonce: u64,     // Successful withdrawals so far; the nonce the next withdrawal may pin
+            pub listed: bool,            // Whether the campaign appears in public listings (unlisted ones still take donations)
+            pub event_level: EventLevel, // Which event each donation emits
+            pub donation_maturity_slots: u64, // Slots a donation stays pending before the admin can spend it (0 = immediately)
+            pub pending_amount: u64,     // Recently donated lamports not yet spendable by the admin
+            pub pending_until_slot: u64, // Slot at which pending_amount matures
+            pub creator: Pubkey,         // Wallet that created the campaign; used in the PDA seeds, never changes
+            pub goal_type: GoalType,     // Whether goal is an amount of lamports or a number of donors
+            pub anti_snipe_window: i64,  // Seconds before the deadline in which a donation extends it (0 = never)
+            pub anti_snipe_extension: i64, // Seconds a late donation pushes the deadline out by
+            pub hard_goal: u64,          // Stretch goal above goal, in the same unit (0 = none)
+            pub withdraw_condition: Pubkey, // Flag account that must read true for withdrawals (default = none)
+            pub withdraw_condition_owner: Pubkey, // Program trusted to own withdraw_condition
+            pub endowment_bps: u16,      // Share of each donation set aside in the endowment (in basis points)
+            pub endowment_total: u64,    // Lamports set aside in the endowment, never withdrawable by the admin; refunds release their share
+            pub denylist: Pubkey,        // Authority whose denylist entries quarantine donations (default = no screening)
+            pub quarantined: u64,        // Lamports from flagged donors held until the config authority releases them
+            pub donation_step: u64,      // Donations must be a multiple of this many lamports (0 = any amount)
+            pub fee_exempt_campaign: bool, // Set by the config authority; donations skip the platform fee entirely
+            pub deadline_mode: DeadlineMode, // Whether the campaign ends at deadline or at deadline_slot
+            pub deadline_slot: u64,      // Slot donations close at in Slot mode (0 in Timestamp mode)
+            pub rent_sweep_target: Pubkey, // Wallet reclaimed rent of auxiliary accounts goes to (default = admin)
+            pub locale: [u8; 2],         // ISO 639-1 language code in lowercase ASCII, e.g. b"en" ([0; 2] = unspecified)
+            pub match_sponsor: Pubkey,    // Wallet that funded the current match pool and may reclaim it (default = none)
+            pub match_expiry: i64,       // Unix timestamp after which the sponsor may reclaim the unused pool (0 = at the deadline)
+            pub thank_you: [u8; 280],    // Creator's thank-you shown to donors after donating, UTF-8, zero-padded
+            pub thank_you_len: u16,      // Bytes of thank_you in use
+            pub max_withdraw_pct_per_period: u16, // Share of amount_donated the admin may move out per period (in basis points, 0 = no cap)
+            pub withdraw_period: i64,    // Length of a withdrawal period in seconds
+            pub period_start: i64,       // Unix timestamp the current withdrawal period started at
+            pub withdrawn_in_period: u64, // Lamports moved out during the current period
+            pub waitlist_next: u64,      // Position the next wallet joining the waitlist gets
+            pub waitlist_head: u64,      // Position of the next waitlisted wallet to promote
+            pub promoted_spots: u32,     // Donor spots held for promoted waitlisters who haven't donated yet
+            pub pending_admin: Pubkey,    // Admin proposed by `transfer_admin`, until they accept (default = none)
+            pub recent_pending_amount: u64, // Lamports donated while pending_amount was still maturing, held on their own schedule
+            pub recent_pending_until_slot: u64, // Slot at which recent_pending_amount matures
+        }
+
+        $(#[$($attr)*])*
+        pub struct DonorRecord {
+            pub campaign: Pubkey,       // Campaign this record belongs to
+            pub donor: Pubkey,          // Wallet that made the donations
+            pub amount_donated: u64,    // Cumulative amount donated by this wallet (in lamports)
+            pub donation_count: u64,    // Number of successful donations from this wallet (saturating)
+            pub last_donated_at: i64,   // Unix timestamp of the last successful donation
+            pub bump: u8,               // PDA bump seed
+            pub donor_hash: [u8; 32],   // sha256(campaign donor_salt || donor), a stable pseudonym within the campaign
+            pub hide_amount: bool,      // Donor's choice to keep their amounts out of public events
+            pub weighted_score: u64,    // Sum of the donor's amounts, each weighted by how early in the campaign it came
+        }
+
+        $(#[$($attr)*])*
+        pub struct Follow {
+            pub user: Pubkey,           // Wallet following the campaign
+            pub campaign: Pubkey,       // Campaign being followed
+            pub bump: u8,               // PDA bump seed
+        }
+
+        $(#[$($attr)*])*
+        pub struct OrgDonation {
+            pub campaign: Pubkey,       // Campaign the donations went to
+            pub org: Pubkey,            // Organization the donations are attributed to
+            pub amount_donated: u64,    // Cumulative amount attributed to the organization (in lamports)
+            pub donation_count: u64,    // Number of donations attributed to the organization (saturating)
+            pub bump: u8,               // PDA bump seed
+        }
+
+        /// Permanent summary of a campaign, written by `archive_snapshot` and never closed
+        $(#[$($attr)*])*
+        pub struct CampaignArchive {
+            pub campaign: Pubkey,       // Campaign the snapshot was taken of
+            pub admin: Pubkey,          // Campaign admin at archival
+            pub name: [u8; 100],        // Campaign name, zero-padded
+            pub name_len: u16,          // Bytes of name in use
+            pub status: CampaignStatus, // Final status of the campaign
+            pub amount_donated: u64,    // Lifetime amount donated (in lamports)
+            pub refunded_amount: u64,   // Amount refunded to donors (in lamports)
+            pub donation_count: u64,    // Number of donations received
+            pub donor_count: u32,       // Donors holding a record at archival
+            pub goal: u64,              // Funding goal in lamports or donors (0 = no goal)
+            pub goal_met: bool,         // Whether the campaign's progress met the goal
+            pub created_at: i64,        // Unix timestamp the campaign was created at
+            pub deadline: i64,          // Unix timestamp the campaign ended at (0 = no deadline)
+            pub archived_at: i64,       // Unix timestamp of the snapshot
+            pub rent_sweep_target: Pubkey, // Wallet close_aux sends reclaimed rent to; refreshed when the campaign closes
+        }
+
+        /// A donor's promise to fund `amount` by `due_by`, closed on fulfillment or expiry
+        $(#[$($attr)*])*
+        pub struct Pledge {
+            pub campaign: Pubkey,       // Campaign the pledge was made to
+            pub donor: Pubkey,          // Wallet that pledged
+            pub amount: u64,            // Promised amount before fees (in lamports)
+            pub due_by: i64,            // Unix timestamp the pledge must be fulfilled by
+            pub created_at: i64,        // Unix timestamp the pledge was made at
+            pub bump: u8,               // PDA bump seed
+        }
+
+        /// An issuer's statement that a wallet passed its verification (e.g. KYC)
+        $(#[$($attr)*])*
+        pub struct Attestation {
+            pub issuer: Pubkey,         // Wallet that issued the attestation
+            pub subject: Pubkey,        // Wallet the attestation vouches for
+            pub issued_at: i64,         // Unix timestamp the attestation was issued at
+            pub expires_at: i64,        // Unix timestamp the attestation stops being valid (0 = never)
+            pub bump: u8,               // PDA bump seed
+        }
+
+        /// Program-wide settings, a singleton created by `initialize_config`
+        $(#[$($attr)*])*
+        pub struct ProgramConfig {
+            pub authority: Pubkey,      // Platform authority (resolves disputes)
+            pub bump: u8,               // PDA bump seed
+            pub fee_bps: u16,           // Platform fee taken from each withdrawal, in basis points (0 = none)
+            pub treasury: Pubkey,       // Receives the platform fee
+        }
+
+        /// Holds a disputed campaign's withdrawals until the config authority resolves the dispute
+        $(#[$($attr)*])*
+        pub struct DisputeEscrow {
+            pub campaign: Pubkey,       // Campaign whose withdrawals are held
+            pub amount: u64,            // Lamports currently held (on top of the escrow's rent)
+            pub bump: u8,               // PDA bump seed
+        }
+
+        /// A donor's comment on a campaign, one PDA per message in posting order
+        $(#[$($attr)*])*
+        pub struct DonationMessage {
+            pub campaign: Pubkey,       // Campaign the message was posted on
+            pub author: Pubkey,         // Donor who posted it
+            pub sequence: u64,          // Position in the campaign's feed, starting at 0
+            pub text: [u8; 280],        // Message text, zero-padded
+            pub text_len: u16,          // Bytes of text in use
+            pub posted_at: i64,         // Unix timestamp the message was posted at
+            pub bump: u8,               // PDA bump seed
+        }
+
+        /// A denylist authority's flag on a wallet; campaigns screening against the authority quarantine its donations
+        $(#[$($attr)*])*
+        pub struct DenylistEntry {
+            pub authority: Pubkey,      // Wallet maintaining the denylist
+            pub subject: Pubkey,        // Flagged wallet
+            pub flagged_at: i64,        // Unix timestamp the wallet was flagged at
+            pub bump: u8,               // PDA bump seed
+        }
+
+        /// Donations from a flagged wallet, held by the campaign until the config authority refunds or accepts them
+        $(#[$($attr)*])*
+        pub struct QuarantinedDonation {
+            pub campaign: Pubkey,       // Campaign holding the funds
+            pub donor: Pubkey,          // Flagged wallet that sent them
+            pub amount: u64,            // Lamports held, summed over the wallet's quarantined donations
+            pub quarantined_at: i64,    // Unix timestamp of the latest quarantined donation
+            pub bump: u8,               // PDA bump seed
+        }
+
+        /// A wallet's place in line for a spot on a campaign at its donor cap
+        $(#[$($attr)*])*
+        pub struct WaitlistEntry {
+            pub campaign: Pubkey,       // Campaign waited on
+            pub donor: Pubkey,          // Waiting wallet
+            pub position: u64,          // Place in line, starting at 0
+            pub joined_at: i64,         // Unix timestamp the wallet joined at
+            pub promoted: bool,         // Whether a spot is held for the wallet; closed when it donates
+            pub bump: u8,               // PDA bump seed
+        }
+    };
+}
+
+/// Expands the campaign audit log: a zero-copy ring buffer of privileged actions.
+/// The entry and log structs take separate attributes, since only the log is an account.
+#[macro_export]
+macro_rules! audit_log_accounts {
+    (entry: $(#[$($entry_attr:tt)*])*, log: $(#[$($log_attr:tt)*])*) => {
+        /// Privileged action recorded in a campaign's audit log
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[repr(u8)]
+        pub enum AuditAction {
+            Withdraw = 1,               // Admin withdrew funds
+            DonateFromCampaign = 2,     // Admin moved funds to another campaign
+            Cancel = 3,                 // Admin cancelled the campaign
+            Reopen = 4,                 // Admin reopened a cancelled campaign
+            SetRefundPolicy = 5,        // Admin changed the refund policy
+            Close = 6,                  // Admin closed the campaign account
+            MergeFunds = 7,             // Admin moved the whole balance to another campaign
+            UpdateMetadata = 8,         // Admin changed the name or description
+            SetAttestation = 9,         // Admin changed the donor attestation requirement
+            ProposeRival = 10,          // Admin challenged another campaign, or withdrew the challenge
+            AcceptRival = 11,           // Admin accepted another campaign's challenge
+            SetListed = 12,             // Admin listed or unlisted the campaign
+            SetEventLevel = 13,         // Admin changed which event donations emit
+            RenounceAdmin = 14,         // Admin gave up the campaign for good
+            SyncDonated = 15,           // Admin credited untracked deposits to amount_donated
+            SetRentSweepTarget = 16,    // Admin changed where reclaimed rent goes
+            Pause = 17,                 // Admin paused the campaign
+            Resume = 18,                // Admin resumed a paused campaign
+            TransferAdmin = 19,         // Admin proposed a new admin, or withdrew the proposal
+            AcceptAdmin = 20,           // Proposed admin accepted and took over the campaign
+            SetFeeExempt = 21,          // Config authority waived or reinstated the campaign's platform fee
+        }
+
+        impl AuditAction {
+            /// Decodes the `action` byte of an audit entry, or `None` for an unused slot
+            pub const fn from_u8(value: u8) -> Option<Self> {
+                match value {
+                    1 => Some(Self::Withdraw),
+                    2 => Some(Self::DonateFromCampaign),
+                    3 => Some(Self::Cancel),
+                    4 => Some(Self::Reopen),
+                    5 => Some(Self::SetRefundPolicy),
+                    6 => Some(Self::Close),
+                    7 => Some(Self::MergeFunds),
+                    8 => Some(Self::UpdateMetadata),
+                    9 => Some(Self::SetAttestation),
+                    10 => Some(Self::ProposeRival),
+                    11 => Some(Self::AcceptRival),
+                    12 => Some(Self::SetListed),
+                    13 => Some(Self::SetEventLevel),
+                    14 => Some(Self::RenounceAdmin),
+                    15 => Some(Self::SyncDonated),
+                    16 => Some(Self::SetRentSweepTarget),
+                    17 => Some(Self::Pause),
+                    18 => Some(Self::Resume),
+                    19 => Some(Self::TransferAdmin),
+                    20 => Some(Self::AcceptAdmin),
+                    21 => Some(Self::SetFeeExempt),
+                    _ => None,
+                }
+            }
+        }
+
+        $(#[$($entry_attr)*])*
+        pub struct AuditEntry {
+            pub actor: Pubkey,          // Wallet that performed the action
+            pub amount: u64,            // Lamports moved by the action (0 if none)
+            pub timestamp: i64,         // Unix timestamp of the action
+            pub action: u8,             // AuditAction discriminant (0 = unused slot)
+            pub padding: [u8; 7],       // Keeps entries 8-byte aligned without implicit padding
+        }
+
+        $(#[$($log_attr)*])*
+        pub struct AuditLog {
+            pub campaign: Pubkey,       // Campaign whose privileged actions are logged
+            pub log_epoch: u64,         // Number of times the buffer has wrapped around (saturating)
+            pub head: u64,              // Slot the next entry is written to
+            pub entries: [AuditEntry; 64], // Ring buffer of the most recent entries
+        }
+    };
+}
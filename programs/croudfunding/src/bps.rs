@@ -0,0 +1,93 @@
+//! Basis-point math shared by every fee and split calculation.
+//!
+//! All results are rounded down; when an amount is split across several
+//! weights, the rounding remainder goes to the first share so the parts
+//! always add back up to the original amount.
+
+/// 100% expressed in basis points
+pub const MAX_BPS: u16 = 10_000;
+
+/// Returns `amount * bps / 10_000`, rounded down, or `None` if `bps` exceeds 100%
+pub fn apply(amount: u64, bps: u16) -> Option<u64> {
+    if bps > MAX_BPS {
+        return None;
+    }
+    // The product fits in u128 and the quotient never exceeds `amount`
+    Some((u128::from(amount) * u128::from(bps) / u128::from(MAX_BPS)) as u64)
+}
+
+/// Returns true if the weights add up to exactly 100%
+pub fn is_full_split(weights: &[u16]) -> bool {
+    weights.iter().map(|w| u32::from(*w)).sum::<u32>() == u32::from(MAX_BPS)
+}
+
+/// Splits `amount` proportionally to `weights` (which must add up to 100%)
+///
+/// Each share is rounded down and the remainder is added to the first share.
+pub fn split<const N: usize>(amount: u64, weights: [u16; N]) -> Option<[u64; N]> {
+    if !is_full_split(&weights) {
+        return None;
+    }
+    let mut shares = [0u64; N];
+    let mut allocated = 0u64;
+    for (share, weight) in shares.iter_mut().zip(weights) {
+        *share = apply(amount, weight)?;
+        allocated += *share;
+    }
+    if let Some(first) = shares.first_mut() {
+        *first += amount - allocated;
+    }
+    Some(shares)
+}
+
+/// Splits `amount` proportionally to a runtime list of `weights` (which must add up to 100%)
+///
+/// Same rounding as `split`: each share is rounded down and the remainder is added to the first.
+pub fn split_weights(amount: u64, weights: &[u16]) -> Option<Vec<u64>> {
+    if !is_full_split(weights) {
+        return None;
+    }
+    let mut shares = weights.iter().map(|weight| apply(amount, *weight)).collect::<Option<Vec<u64>>>()?;
+    let allocated: u64 = shares.iter().sum();
+    if let Some(first) = shares.first_mut() {
+        *first += amount - allocated;
+    }
+    Some(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_rounds_down_and_rejects_more_than_100_percent() {
+        assert_eq!(apply(999, 100), Some(9));
+        assert_eq!(apply(u64::MAX, MAX_BPS), Some(u64::MAX));
+        assert_eq!(apply(u64::MAX, 5_000), Some(u64::MAX / 2));
+        assert_eq!(apply(1, MAX_BPS + 1), None);
+    }
+
+    #[test]
+    fn split_gives_the_remainder_to_the_first_share() {
+        assert_eq!(split(10, [3_333, 3_333, 3_334]), Some([4, 3, 3]));
+        assert_eq!(split(u64::MAX, [5_000, 5_000]), Some([u64::MAX / 2 + 1, u64::MAX / 2]));
+        assert_eq!(split(0, [7_000, 3_000]), Some([0, 0]));
+    }
+
+    #[test]
+    fn split_requires_weights_adding_up_to_100_percent() {
+        assert_eq!(split(100, [5_000, 4_999]), None);
+        assert_eq!(split(100, [MAX_BPS, 1]), None);
+        assert_eq!(split(100, [u16::MAX, u16::MAX]), None);
+        assert_eq!(split::<0>(100, []), None);
+    }
+
+    #[test]
+    fn split_weights_matches_split() {
+        let weights = [2_500, 2_500, 4_999, 1];
+        assert_eq!(split_weights(1_000_003, &weights), split(1_000_003, weights).map(Vec::from));
+        assert_eq!(split_weights(7, &[MAX_BPS]), Some(vec![7]));
+        assert_eq!(split_weights(7, &[]), None);
+        assert_eq!(split_weights(7, &[9_000]), None);
+    }
+}
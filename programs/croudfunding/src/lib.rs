@@ -1,9 +1,36 @@
 use anchor_lang::prelude::*; // Anchor framework's standard imports
+use anchor_lang::solana_program::hash::hashv; // Used for pseudonymous donor hashes
 use anchor_lang::solana_program::rent::Rent; // Used for rent exemption calculation
 
+pub mod bps; // Shared basis-point math for fees and splits
+
+// Seeds, discriminators and byte offsets published by the types crate
+pub use croudfunding_types::constants::*;
+
 // Declare the program ID (public key of your deployed program)
 declare_id!("5Gbm8uSMg1i6Agj9NqcccywoCKPEiVvBWRC2RVUsDjHL");
 
+/// Maximum number of campaigns `create_many` initializes in one instruction
+pub const MAX_CREATE_BATCH: usize = 5;
+
+/// Maximum number of campaigns `donate_weighted` splits one gift across
+pub const MAX_WEIGHTED_CAMPAIGNS: usize = 5;
+
+/// Maximum number of withdrawal destinations a campaign can commit to
+pub const MAX_ALLOWED_DESTINATIONS: usize = 3;
+
+/// Maximum share of a cranked refund paid to the cranker, in basis points (1%)
+pub const MAX_CRANK_BOUNTY_BPS: u16 = 100;
+
+/// Maximum platform fee the config authority can set, in basis points (10%)
+pub const MAX_PLATFORM_FEE_BPS: u16 = 1_000;
+
+/// Maximum headroom `create` may allocate beyond `Campaign::LEN`, in bytes
+pub const MAX_EXTRA_SPACE: u16 = 2048;
+
+/// Latest a deadline can be pushed by anti-sniping extensions, in seconds after creation (1 year)
+pub const MAX_DURATION: i64 = 365 * 24 * 60 * 60;
+
 #[program]
 pub mod croudfunding {
     use super::*;
@@ -12,55 +39,206 @@ pub mod croudfunding {
     ///
     /// # Arguments
     /// * `ctx` - The context holding all accounts involved in this instruction
-    /// * `name` - The name of the campaign
-    /// * `description` - A short description of the campaign
-    pub fn create(ctx: Context<Create>, name: String, description: String) -> Result<()> {
+    /// * `params` - The campaign's metadata and configuration (see `CampaignParams`)
+    pub fn create(ctx: Context<Create>, params: CampaignParams) -> Result<()> {
         let campaign = &mut ctx.accounts.campaign;
 
-        campaign.name = name;
-        campaign.description = description;
-        campaign.amount_donated = 0;
-        campaign.admin = ctx.accounts.user.key(); // Set creator as admin
+        // A retried submission lands on the already initialized account instead of creating a duplicate
+        // (checked on the version, since a renounced campaign has no admin)
+        if campaign.version != 0 {
+            return Err(ErrorCode::AlreadyCreated.into());
+        }
+
+        let key = campaign.key();
+        let data_len = campaign.to_account_info().data_len();
+        campaign.init(key, params, ctx.accounts.user.key(), data_len, ctx.bumps.campaign)?; // Set creator as admin
+        emit_created(key, campaign)?;
 
         msg!("Campaign created successfully");
         Ok(())
     }
 
+    /// Creates several campaigns for the signer in one instruction
+    ///
+    /// The campaign PDAs are passed in `remaining_accounts`, in the same order as
+    /// `params`; each must match `[b"campaign", user key, params.idempotency_key]` (see `Create`).
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the payer; campaign PDAs go in `remaining_accounts`
+    /// * `params` - One `CampaignParams` per campaign, at least one and at most `MAX_CREATE_BATCH`
+    pub fn create_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateMany<'info>>,
+        params: Vec<CampaignParams>,
+    ) -> Result<()> {
+        if params.is_empty() {
+            return Err(ErrorCode::EmptyBatch.into());
+        }
+        if params.len() > MAX_CREATE_BATCH {
+            return Err(ErrorCode::BatchTooLarge.into());
+        }
+        if params.len() != ctx.remaining_accounts.len() {
+            return Err(ErrorCode::BatchMismatch.into());
+        }
+
+        let user = &ctx.accounts.user;
+        let rent = Rent::get()?;
+        for (params, info) in params.into_iter().zip(ctx.remaining_accounts) {
+            // Each account must be the PDA `create` would have derived for these params
+            let (expected, bump) = Pubkey::find_program_address(
+                &[CAMPAIGN_SEED, user.key().as_ref(), croudfunding_types::campaign_seed_key(&params.idempotency_key)],
+                ctx.program_id,
+            );
+            if info.key() != expected {
+                return Err(ErrorCode::BatchMismatch.into());
+            }
+            if info.lamports() > 0 || !info.data_is_empty() {
+                return Err(ErrorCode::AlreadyCreated.into());
+            }
+
+            let space = Campaign::LEN + usize::from(params.extra_space);
+            let signer_seeds: &[&[u8]] = &[CAMPAIGN_SEED, user.key.as_ref(), croudfunding_types::campaign_seed_key(&params.idempotency_key), &[bump]];
+            anchor_lang::system_program::create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::CreateAccount {
+                        from: user.to_account_info(),
+                        to: info.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                rent.minimum_balance(space),
+                space as u64,
+                ctx.program_id,
+            )?;
+
+            // The fresh account is zeroed, which decodes as an empty campaign
+            let mut campaign = Campaign::try_deserialize_unchecked(&mut &info.try_borrow_data()?[..])?;
+            campaign.init(info.key(), params, user.key(), space, bump)?;
+            campaign.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+            emit_created(info.key(), &campaign)?;
+        }
+
+        msg!("Campaigns created successfully");
+        Ok(())
+    }
+
     /// Withdraws funds from a campaign
     ///
+    /// Funds go to `destination` if given, otherwise to the admin; either way the
+    /// recipient must be in the campaign's allowlist when it has one. A campaign with a goal and
+    /// a deadline only pays out once it meets the goal or the deadline passes, and funds that
+    /// donors can still claim back (an all-or-nothing campaign that fails, or a cancelled one)
+    /// stay reserved for refunds.
+    /// While the campaign is disputed, funds go to its `DisputeEscrow` instead. A campaign with
+    /// a `withdraw_condition` only pays out while that flag account reads true. Once the program
+    /// config exists, the platform fee set by `set_platform_fee` goes to the treasury, unless the
+    /// campaign is fee-exempt or has an allowlist (its funds may only reach the listed addresses).
+    ///
     /// # Arguments
-    /// * `ctx` - The context holding the campaign and user accounts
+    /// * `ctx` - The context holding the campaign, admin and optional destination accounts
     /// * `amount` - The amount to withdraw in lamports
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()>  {
-        let campaign = &mut ctx.accounts.campaign;
-        let user = &ctx.accounts.user;
+    /// * `withdraw_nonce` - If given, must equal the campaign's `withdraw_nonce`, so a retried
+    ///   submission of an already-applied withdrawal fails instead of draining again
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64, withdraw_nonce: Option<u64>) -> Result<()>  {
+        withdraw_funds(ctx, amount, withdraw_nonce, false)
+    }
 
-        // Only the admin can withdraw funds
-        if campaign.admin != user.key() {
-            return Err(ErrorCode::Unauthorized.into());
+    // This function handles the donation logic: transferring SOL from the user to the campaign account.
+    // If `org` is given, the donation is also attributed to that organization's `OrgDonation` PDA.
+    // The returned `DonateResult` is exposed as return data to simulating wallets and CPI callers.
+    // If `expected_admin` is given, the donation is rejected unless the campaign is still run by that admin.
+    // With `protect_donor_rent`, the donation is capped so the donor's wallet stays rent-exempt.
+    // On a campaign screening against a denylist, a flagged donor's lamports are quarantined
+    // (without fees) instead of counted, until the config authority calls `release_quarantine`.
+    pub fn donate(
+        ctx: Context<Donate>,
+        amount: u64,
+        org: Option<Pubkey>,
+        expected_admin: Option<Pubkey>,
+        protect_donor_rent: bool,
+    ) -> Result<DonateResult> {
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.campaign.check_accepting_donations(now, Clock::get()?.slot)?;
+
+        // Let cautious donors pin the admin they intended to support
+        if expected_admin.is_some_and(|admin| admin != ctx.accounts.campaign.admin) {
+            return Err(ErrorCode::AdminChanged.into());
         }
 
-        // Calculate the minimum balance required to keep the account rent-exempt
-        let rent_balance = Rent::get()?.minimum_balance(Campaign::LEN);
+        // The org PDA is derived from `org`, so one can't be passed without the other
+        if org.is_some() != ctx.accounts.org_donation.is_some() {
+            return Err(ErrorCode::OrgMismatch.into());
+        }
 
-        // Current lamports in the campaign account
-        let campaign_lamports = **campaign.to_account_info().lamports.borrow();
+        ctx.accounts.campaign.check_attestation(ctx.accounts.attestation.as_deref(), &ctx.accounts.user.key(), now)?;
 
-        // Check if enough lamports are available to withdraw
-        if campaign_lamports - rent_balance < amount {
-            return Err(ErrorCode::InsufficientFunds.into());
+        // A promoted waitlister may use the spot held for them (taken once the donation is credited)
+        if let Some(entry) = &ctx.accounts.waitlist_entry {
+            if !entry.promoted {
+                return Err(ErrorCode::NotPromoted.into());
+            }
+        } else {
+            ctx.accounts.campaign.check_donor_capacity(ctx.accounts.donor_record.donation_count == 0)?;
         }
 
-        // Transfer lamports from campaign to user
-        **campaign.to_account_info().try_borrow_mut_lamports()? -= amount;
-        **user.to_account_info().try_borrow_mut_lamports()? += amount;
+        // Reject donations arriving faster than the campaign allows (a wallet's first donation is always allowed)
+        ctx.accounts.donor_record.check_rate_limit(ctx.accounts.campaign.min_seconds_between_donations, now)?;
 
-        msg!("Withdrawal successful");
-        Ok(())
-    }
+        // Leave the donor enough to keep their own wallet rent-exempt, in whole donation steps
+        let amount = if protect_donor_rent {
+            let mut spendable = ctx.accounts.user.lamports().saturating_sub(Rent::get()?.minimum_balance(0));
+            if ctx.accounts.campaign.donation_step != 0 {
+                spendable -= spendable % ctx.accounts.campaign.donation_step;
+            }
+            if spendable == 0 {
+                return Err(ErrorCode::WouldUnfundDonor.into());
+            }
+            amount.min(spendable)
+        } else {
+            amount
+        };
+        ctx.accounts.campaign.check_donation_step(amount)?;
+
+        // Hold donations from flagged wallets apart from the campaign's totals
+        if ctx.accounts.campaign.check_denylist(ctx.accounts.denylist_entry.as_ref().map(|e| e.as_ref()))? {
+            let quarantine = ctx.accounts.quarantine.as_mut().ok_or(ErrorCode::QuarantineRequired)?;
+            quarantine_donation(
+                &mut ctx.accounts.campaign,
+                &mut ctx.accounts.donor_record,
+                quarantine,
+                &ctx.accounts.user.to_account_info(),
+                amount,
+                now,
+                (ctx.bumps.donor_record, ctx.bumps.quarantine.unwrap_or_default()),
+            )?;
+
+            msg!("Donation quarantined");
+            let campaign = &ctx.accounts.campaign;
+            let total_hidden = campaign.total_hidden(now);
+            return Ok(DonateResult {
+                net_amount: 0,
+                new_total: if total_hidden { 0 } else { campaign.amount_donated },
+                donor_total: ctx.accounts.donor_record.amount_donated,
+                receipt_number: campaign.donation_count,
+                goal_reached: !total_hidden && campaign.goal_reached(),
+                total_hidden,
+            });
+        }
+
+        // The donation counts, so a promoted waitlister takes their spot and their entry is closed;
+        // a quarantined donation above leaves both in place
+        if let Some(entry) = &ctx.accounts.waitlist_entry {
+            ctx.accounts.campaign.promoted_spots = ctx.accounts.campaign.promoted_spots.saturating_sub(1);
+            entry.close(ctx.accounts.user.to_account_info())?;
+        }
+
+        // Split the platform fee off the donation and pay each recipient its share
+        let fee_accounts = [&ctx.accounts.fee_recipient_a, &ctx.accounts.fee_recipient_b];
+        let amount = pay_fees(&mut ctx.accounts.campaign, &ctx.accounts.user.to_account_info(), fee_accounts, amount)?; // Only the net amount reaches the campaign
+
+        #[cfg(feature = "strict-invariants")]
+        let balance_before = ctx.accounts.campaign.to_account_info().lamports();
 
-    // This function handles the donation logic: transferring SOL from the user to the campaign account
-    pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
         // Create a transfer instruction using Solana's system program
         let ix = anchor_lang::solana_program::system_instruction::transfer(
             &ctx.accounts.user.key(),      // Sender (donor) public key
@@ -77,78 +255,4917 @@ pub mod croudfunding {
             ],
         )?;
 
-        // Update the total amount donated in the campaign account
-        ctx.accounts.campaign.amount_donated += amount;
+        // Update the campaign totals, match the donation from the sponsor pool and extend a late deadline
+        let campaign_key = ctx.accounts.campaign.key();
+        let donor_key = ctx.accounts.user.key();
+        let donor_hash = ctx.accounts.campaign.donor_hash(&donor_key);
+        let first_donation = ctx.accounts.donor_record.donation_count == 0;
+        let hide_amount = ctx.accounts.donor_record.hide_amount;
+        credit_donation(&mut ctx.accounts.campaign, donor_hash, amount, hide_amount, first_donation, now, Clock::get()?.slot)?;
+        #[cfg(feature = "strict-invariants")]
+        check_inflow(&ctx.accounts.campaign.to_account_info(), balance_before, amount)?;
+
+        // Record the donation against the donor; the timestamp only moves on a successful donation
+        ctx.accounts.donor_record.record_donation(campaign_key, donor_key, donor_hash, amount, now, ctx.bumps.donor_record)?;
+        ctx.accounts.donor_record.weighted_score = ctx.accounts.donor_record.weighted_score.saturating_add(ctx.accounts.campaign.time_weighted(amount, now));
+
+        // Credit the organization the donor gives on behalf of
+        if let (Some(org), Some(org_donation)) = (org, ctx.accounts.org_donation.as_mut()) {
+            org_donation.campaign = campaign_key;
+            org_donation.org = org;
+            org_donation.amount_donated = org_donation.amount_donated.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+            org_donation.donation_count = org_donation.donation_count.saturating_add(1);
+            org_donation.bump = ctx.bumps.org_donation.unwrap_or_default();
+        }
+
+        emit_donation(&ctx.accounts.campaign, donor_hash, amount, ctx.accounts.donor_record.hide_amount, now);
+        let total_hidden = ctx.accounts.campaign.total_hidden(now);
 
         // Print a success message in the program log
         msg!("Donation successful");
 
-        // Return success
+        // Return the outcome; Anchor sets it as return data (read back with `get_return_data`)
+        Ok(DonateResult {
+            net_amount: amount,
+            new_total: if total_hidden { 0 } else { ctx.accounts.campaign.amount_donated },
+            donor_total: ctx.accounts.donor_record.amount_donated,
+            receipt_number: ctx.accounts.campaign.donation_count,
+            goal_reached: !total_hidden && ctx.accounts.campaign.goal_reached(),
+            total_hidden,
+        })
+    }
+
+    /// Donates funds held by one campaign to another campaign
+    ///
+    /// The source campaign's admin signs; the source campaign's pubkey is recorded as the donor
+    /// on the destination so the transfer is fully attributed. The destination credits it like
+    /// any other donation: endowment, maturity, matching, verified totals and the late-donation
+    /// deadline extension all apply.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the source, destination and donor record accounts
+    /// * `amount` - The amount to move in lamports
+    pub fn donate_from_campaign(ctx: Context<DonateFromCampaign>, amount: u64) -> Result<()> {
+        let source = &ctx.accounts.source;
+        let destination = &ctx.accounts.destination;
+
+        // A campaign cannot donate to itself
+        if source.key() == destination.key() {
+            return Err(ErrorCode::SameCampaign.into());
+        }
+
+        // Only the source campaign's admin can move its funds
+        if source.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        // The source's funds must be free to move, and only a live destination takes donations
+        if source.disputed {
+            return Err(ErrorCode::CampaignDisputed.into());
+        }
+        let now = Clock::get()?.unix_timestamp;
+        source.check_withdrawable(now, Clock::get()?.slot)?;
+        destination.check_accepting_donations(now, Clock::get()?.slot)?;
+        destination.check_donor_capacity(ctx.accounts.donor_record.donation_count == 0)?;
+        destination.check_donation_step(amount)?;
+
+        // Routing to another campaign is an outflow too, so it honours the source's allowlist
+        source.check_destination(&destination.key())?;
+
+        // The source must stay rent-exempt and keep its match pool after the transfer
+        let source_lamports = **source.to_account_info().lamports.borrow();
+        if source.spendable_lamports(source_lamports, Clock::get()?.slot)? < amount {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+
+        // Both accounts are owned by this program, so lamports can be moved directly
+        move_lamports(&source.to_account_info(), &destination.to_account_info(), amount)?;
+
+        let source_key = source.key();
+        let destination_key = destination.key();
+
+        ctx.accounts.source.amount_withdrawn = ctx.accounts.source.amount_withdrawn.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        ctx.accounts.source.record_period_withdrawal(amount, now)?;
+
+        // The destination books it like any other donation, matching and endowment included
+        let destination = &mut ctx.accounts.destination;
+        let donor_hash = destination.donor_hash(&source_key);
+        let donor_record = &ctx.accounts.donor_record;
+        credit_donation(destination, donor_hash, amount, donor_record.hide_amount, donor_record.donation_count == 0, now, Clock::get()?.slot)?;
+        ctx.accounts.donor_record.record_donation(destination_key, source_key, donor_hash, amount, now, ctx.bumps.donor_record)?;
+        ctx.accounts.donor_record.weighted_score = ctx.accounts.donor_record.weighted_score.saturating_add(destination.time_weighted(amount, now));
+
+        // Each side's event references the other campaign for traceability
+        emit!(CampaignDonationSent {
+            source: source_key,
+            destination: destination_key,
+            amount,
+            timestamp: now,
+        });
+        emit!(CampaignDonationReceived {
+            destination: destination_key,
+            source: source_key,
+            amount,
+            new_total: if destination.total_hidden(now) { 0 } else { destination.amount_donated },
+            total_hidden: destination.total_hidden(now),
+            timestamp: now,
+        });
+
+        audit(&ctx.accounts.audit_log, source_key, AuditAction::DonateFromCampaign, ctx.accounts.user.key(), amount)?;
+
+        msg!("Campaign donation successful");
         Ok(())
     }
 
-}
+    /// Follows a campaign, creating the user's `Follow` PDA
+    ///
+    /// Following twice fails because the PDA already exists.
+    pub fn follow_campaign(ctx: Context<FollowCampaign>) -> Result<()> {
+        let follow = &mut ctx.accounts.follow;
+        follow.user = ctx.accounts.user.key();
+        follow.campaign = ctx.accounts.campaign.key();
+        follow.bump = ctx.bumps.follow;
 
-#[derive(Accounts)]
-pub struct Create<'info> {
-    /// Initializes the campaign account with PDA (Program Derived Address)
-    /// Uses seeds = [b"campaign", user key] to derive unique address
-    #[account(
-        init,
-        payer = user,
-        space = Campaign::LEN, // Allocate fixed space for Campaign struct
-        seeds = [b"campaign", user.key().as_ref()],
-        bump
-    )]
-    pub campaign: Account<'info, Campaign>, // Mutable new campaign account
+        ctx.accounts.campaign.follower_count = ctx.accounts.campaign.follower_count.saturating_add(1);
 
-    #[account(mut)]
-    pub user: Signer<'info>, // The user creating the campaign (payer and signer)
+        msg!("Campaign followed");
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>, // Required for account creation and rent
-}
+    /// Unfollows a campaign, closing the `Follow` PDA and refunding its rent to the user
+    pub fn unfollow_campaign(ctx: Context<UnfollowCampaign>) -> Result<()> {
+        ctx.accounts.campaign.follower_count = ctx.accounts.campaign.follower_count.saturating_sub(1);
 
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(mut)]
-    pub campaign: Account<'info, Campaign>, // Mutable campaign account for withdrawal
+        msg!("Campaign unfollowed");
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub user: Signer<'info>, // The user (must be campaign admin)
-}
+    /// Migrates a campaign account from the original `String` layout to the current layout
+    ///
+    /// Original campaigns live at `[b"campaign", admin]` and only ever stored their name,
+    /// description, amount donated and admin; only that admin can migrate one. The account
+    /// grows to `Campaign::LEN`, with the admin paying the extra rent, and keeps its address
+    /// because a campaign without an idempotency key derives the same one.
+    pub fn migrate_campaign(ctx: Context<MigrateCampaign>) -> Result<()> {
+        let info = ctx.accounts.campaign.to_account_info();
 
-#[derive(Accounts)]
-pub struct Donate<'info> {
-    #[account(mut)]
-    pub campaign: Account<'info, Campaign>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+        // Legacy accounts are recognised by their allocation size and Campaign discriminator
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            if data.len() != LegacyCampaign::LEN {
+                return Err(ErrorCode::AlreadyMigrated.into());
+            }
+            if !data.starts_with(Campaign::DISCRIMINATOR) {
+                return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+            }
+            LegacyCampaign::deserialize(&mut &data[DISCRIMINATOR_LEN..])?
+        };
 
-#[account]
-pub struct Campaign {
-    pub name: String,           // Campaign name (max size must be estimated)
-    pub description: String,    // Campaign description
-    pub amount_donated: u64,    // Total amount donated (in lamports)
-    pub admin: Pubkey,          // Admin (creator) of the campaign
-}
+        // Only the admin can migrate the campaign
+        if legacy.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
 
-impl Campaign {
-    /// Fixed size of the Campaign account in bytes
-    /// - 8 bytes for discriminator (Anchor adds this automatically)
-    /// - 4 + 100 for name (4-byte prefix for length, 100 bytes max content)
-    /// - 4 + 500 for description
-    /// - 8 bytes for u64 amount_donated
-    /// - 32 bytes for Pubkey admin
-    pub const LEN: usize = 8 + 4 + 100 + 4 + 500 + 8 + 32;
-}
+        // The original withdraw kept the old rent minimum and didn't count withdrawals, so
+        // whatever donated lamports are missing above that minimum were withdrawn
+        let rent = Rent::get()?;
+        let legacy_rent = rent.minimum_balance(LegacyCampaign::LEN);
+        let amount_withdrawn = legacy.amount_donated.saturating_sub(info.lamports().saturating_sub(legacy_rent));
+
+        let mut campaign = Campaign {
+            version: CAMPAIGN_VERSION,
+            name: [0; MAX_NAME_LEN],
+            name_len: 0,
+            description: [0; MAX_DESCRIPTION_LEN],
+            description_len: 0,
+            amount_donated: legacy.amount_donated,
+            admin: legacy.admin,
+            min_seconds_between_donations: 0,
+            donation_count: 0,
+            idempotency_key: [0; 16],
+            fee_bps: 0,
+            fee_recipients: [FeeRecipient::default(); 2],
+            rent_minimum: rent.minimum_balance(Campaign::LEN),
+            follower_count: 0,
+            goal: 0,
+            deadline: 0,
+            created_at: 0,
+            refund_delay: 0,
+            refunded_amount: 0,
+            status: CampaignStatus::Active,
+            reopen_grace_period: 0,
+            cancelled_at: 0,
+            reopen_until: 0,
+            donor_salt: hashv(&[b"donor-salt", info.key.as_ref()]).to_bytes(),
+            // Original donations left no donor records to refund against
+            refund_policy: RefundPolicy::None,
+            allowed_destinations: [Pubkey::default(); MAX_ALLOWED_DESTINATIONS],
+            allowed_destination_count: 0,
+            verification_min: 0,
+            verified_raised: 0,
+            allocated_space: Campaign::LEN as u32,
+            match_pool: 0,
+            matched_total: 0,
+            donor_count: 0,
+            currency_symbol: [0; MAX_CURRENCY_SYMBOL_LEN],
+            currency_symbol_len: 0,
+            amount_withdrawn,
+            pledged_total: 0,
+            crank_bounty_bps: 0,
+            min_donors: 0,
+            require_attestation: false,
+            attestation_issuer: Pubkey::default(),
+            disputed: false,
+            max_donors: 0,
+            dust_threshold: 0,
+            withdraw_cliff: 0,
+            rival: Pubkey::default(),
+            proposed_rival: Pubkey::default(),
+            accrue_fees: false,
+            fees_accrued: [0; 2],
+            message_count: 0,
+            hide_total_until_deadline: false,
+            withdraw_nonce: 0,
+            // Legacy campaigns were all publicly listed
+            listed: true,
+            event_level: EventLevel::Full,
+            donation_maturity_slots: 0,
+            pending_amount: 0,
+            pending_until_slot: 0,
+            creator: legacy.admin,
+            goal_type: GoalType::Amount,
+            anti_snipe_window: 0,
+            anti_snipe_extension: 0,
+            hard_goal: 0,
+            withdraw_condition: Pubkey::default(),
+            withdraw_condition_owner: Pubkey::default(),
+            endowment_bps: 0,
+            endowment_total: 0,
+            denylist: Pubkey::default(),
+            quarantined: 0,
+            donation_step: 0,
+            fee_exempt_campaign: false,
+            deadline_mode: DeadlineMode::Timestamp,
+            deadline_slot: 0,
+            rent_sweep_target: Pubkey::default(),
+            locale: [0; 2],
+            match_sponsor: Pubkey::default(),
+            match_expiry: 0,
+            thank_you: [0; MAX_THANK_YOU_LEN],
+            thank_you_len: 0,
+            max_withdraw_pct_per_period: 0,
+            withdraw_period: 0,
+            period_start: 0,
+            withdrawn_in_period: 0,
+            waitlist_next: 0,
+            waitlist_head: 0,
+            promoted_spots: 0,
+            pending_admin: Pubkey::default(),
+            recent_pending_amount: 0,
+            recent_pending_until_slot: 0,
+            // Legacy campaigns keep their address, they just didn't store the bump
+            bump: ctx.bumps.campaign,
+        };
+        campaign.set_name(&legacy.name)?;
+        campaign.set_description(&legacy.description)?;
+        campaign.set_currency_symbol(DEFAULT_CURRENCY_SYMBOL)?;
+
+        // Grow to the new size, with the admin covering the extra rent so donated lamports stay put
+        let extra_rent = campaign.rent_minimum.saturating_sub(legacy_rent);
+        if extra_rent > 0 {
+            transfer_lamports(&ctx.accounts.user.to_account_info(), &info, extra_rent)?;
+        }
+        info.realloc(Campaign::LEN, false)?;
+        campaign.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        msg!("Campaign migrated to version {}", CAMPAIGN_VERSION);
+        Ok(())
+    }
+
+    /// Refunds the caller's donations as allowed by the campaign's refund policy
+    ///
+    /// See `Campaign::check_refund_allowed` for when refunds are open. A refund is capped
+    /// by the campaign's uncommitted balance; the donor record is closed (returning its
+    /// rent) once the donor's whole contribution has been refunded.
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let now = Clock::get()?.unix_timestamp;
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("You are not authorized to perform this action.")]
-    Unauthorized, // Returned when a non-admin tries to withdraw
+        campaign.check_refund_allowed(now, Clock::get()?.slot)?;
 
-    #[msg("Not enough funds in the campaign account.")]
-    InsufficientFunds, // Returned when withdrawal amount exceeds available balance
+        let contributed = ctx.accounts.donor_record.amount_donated;
+        let campaign_lamports = **campaign.to_account_info().lamports.borrow();
+        let available = campaign.available_lamports(campaign_lamports)?;
+        let amount = match campaign.refund_policy {
+            // Self-refunds on a live campaign can only take what the admin hasn't withdrawn
+            RefundPolicy::UntilDeadline => contributed.min(available),
+            _ => contributed,
+        };
+        if amount == 0 || available < amount {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+
+        // Return the donor's lamports
+        move_lamports(&campaign.to_account_info(), &ctx.accounts.user.to_account_info(), amount)?;
+        campaign.record_refund(amount)?;
+
+        let donor_record = &mut ctx.accounts.donor_record;
+        donor_record.amount_donated = donor_record.amount_donated.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        if donor_record.amount_donated == 0 {
+            donor_record.close(ctx.accounts.user.to_account_info())?;
+            campaign.donor_count = campaign.donor_count.saturating_sub(1); // Migrated campaigns start counting at 0
+        }
+
+        if campaign.recompute_status(now, Clock::get()?.slot) {
+            emit_finalized(campaign, now);
+        }
+
+        msg!("Refund successful");
+        Ok(())
+    }
+
+    /// Changes the campaign's refund policy; only possible before the first donation
+    pub fn set_refund_policy(ctx: Context<UpdateCampaign>, refund_policy: RefundPolicy) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can change the refund policy
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        // Donors gave under the current policy, so it's frozen from the first donation on
+        if campaign.donation_count > 0 {
+            return Err(ErrorCode::RefundPolicyLocked.into());
+        }
+
+        campaign.refund_policy = refund_policy;
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::SetRefundPolicy, ctx.accounts.user.key(), 0)?;
+
+        msg!("Refund policy updated");
+        Ok(())
+    }
+
+    /// Cancels an active or paused campaign, stopping donations and opening refunds
+    ///
+    /// The admin may undo the cancellation with `reopen_campaign` until
+    /// `reopen_grace_period` seconds have passed, as long as no refund has been paid.
+    pub fn cancel_campaign(ctx: Context<UpdateCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Only the admin can cancel the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if !matches!(campaign.status, CampaignStatus::Active | CampaignStatus::Paused) {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        campaign.status = CampaignStatus::Cancelled;
+        campaign.cancelled_at = now;
+        campaign.reopen_until = now.saturating_add(campaign.reopen_grace_period);
+
+        emit!(CampaignCancelled {
+            campaign: campaign.key(),
+            cancelled_at: now,
+            reopen_until: campaign.reopen_until,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::Cancel, ctx.accounts.user.key(), 0)?;
+
+        msg!("Campaign cancelled");
+        Ok(())
+    }
+
+    /// Reopens a cancelled campaign within its grace period
+    ///
+    /// Impossible once any refund has been paid. The deadline is left untouched.
+    pub fn reopen_campaign(ctx: Context<UpdateCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Only the admin can reopen the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if campaign.status != CampaignStatus::Cancelled {
+            return Err(ErrorCode::CampaignNotCancelled.into());
+        }
+
+        // Donors who already took their money back can't be pulled into a revived campaign
+        if campaign.refunded_amount > 0 {
+            return Err(ErrorCode::ReopenAfterRefund.into());
+        }
+        if now > campaign.reopen_until {
+            return Err(ErrorCode::ReopenWindowClosed.into());
+        }
+
+        campaign.status = CampaignStatus::Active;
+
+        emit!(CampaignReopened {
+            campaign: campaign.key(),
+            cancelled_at: campaign.cancelled_at,
+            timestamp: now,
+        });
+
+        // A campaign reopened past its deadline ends right away
+        if campaign.recompute_status(now, Clock::get()?.slot) {
+            emit_finalized(campaign, now);
+        }
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::Reopen, ctx.accounts.user.key(), 0)?;
+
+        msg!("Campaign reopened");
+        Ok(())
+    }
+
+    /// Pauses an active campaign, holding donations and withdrawals
+    ///
+    /// Refunds keep following the refund policy, and the deadline keeps running: a campaign
+    /// resumed past its deadline ends right away.
+    pub fn pause_campaign(ctx: Context<UpdateCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Only the admin can pause the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if campaign.status != CampaignStatus::Active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        campaign.status = CampaignStatus::Paused;
+
+        emit!(CampaignPauseChanged {
+            campaign: campaign.key(),
+            paused: true,
+            timestamp: now,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::Pause, ctx.accounts.user.key(), 0)?;
+
+        msg!("Campaign paused");
+        Ok(())
+    }
+
+    /// Resumes a paused campaign
+    pub fn resume_campaign(ctx: Context<UpdateCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Only the admin can resume the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if campaign.status != CampaignStatus::Paused {
+            return Err(ErrorCode::CampaignNotPaused.into());
+        }
+
+        campaign.status = CampaignStatus::Active;
+
+        emit!(CampaignPauseChanged {
+            campaign: campaign.key(),
+            paused: false,
+            timestamp: now,
+        });
+
+        // A campaign resumed past its deadline ends right away
+        if campaign.recompute_status(now, Clock::get()?.slot) {
+            emit_finalized(campaign, now);
+        }
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::Resume, ctx.accounts.user.key(), 0)?;
+
+        msg!("Campaign resumed");
+        Ok(())
+    }
+
+    /// Finalizes a campaign once its deadline has passed
+    ///
+    /// Permissionless: anyone can close out the campaign as `Successful` if it met its goal
+    /// (or has none) and its minimum number of donors, or `Failed` otherwise. A successful all-or-nothing campaign's funds
+    /// become withdrawable; a failed one's remain reserved for refunds.
+    pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let now = Clock::get()?.unix_timestamp;
+
+        if campaign.status != CampaignStatus::Active {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+        // Campaigns without a deadline never end on their own
+        if !campaign.recompute_status(now, Clock::get()?.slot) {
+            return Err(ErrorCode::CampaignNotEnded.into());
+        }
+        emit_finalized(campaign, now);
+
+        msg!("Campaign finalized");
+        Ok(())
+    }
+
+    /// Adds sponsor funds to the campaign's match pool
+    ///
+    /// Each later donation is matched one-to-one from the pool until it runs out.
+    /// Unspent pool funds are excluded from withdrawals and refunds, and the sponsor can
+    /// take them back with `reclaim_match` once the pool expires; if the campaign fails or is
+    /// cancelled, the matched amounts go back to the sponsor as well. A pool has one sponsor
+    /// at a time: until its funds, matched ones included, have gone back to the sponsor,
+    /// only that wallet can top it up.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the campaign and sponsor accounts
+    /// * `amount` - The amount to add to the pool in lamports
+    pub fn fund_match_pool(ctx: Context<FundMatchPool>, amount: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.campaign.check_accepting_donations(clock.unix_timestamp, clock.slot)?;
+
+        let sponsor = ctx.accounts.sponsor.key();
+        let campaign = &ctx.accounts.campaign;
+        if (campaign.match_pool > 0 || campaign.matched_total > 0) && campaign.match_sponsor != sponsor {
+            return Err(ErrorCode::MatchSponsorMismatch.into());
+        }
+
+        transfer_lamports(&ctx.accounts.sponsor.to_account_info(), &ctx.accounts.campaign.to_account_info(), amount)?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.match_pool = campaign.match_pool.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        campaign.match_sponsor = sponsor;
+
+        emit!(MatchPoolFunded {
+            campaign: campaign.key(),
+            sponsor: ctx.accounts.sponsor.key(),
+            amount,
+            match_pool: campaign.match_pool,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Match pool funded");
+        Ok(())
+    }
+
+    /// Records a permanent `CampaignArchive` snapshot of an ended campaign
+    ///
+    /// The archive is created once and never closed, so the campaign's history survives
+    /// `close_campaign` reclaiming the main account's rent.
+    pub fn archive_snapshot(ctx: Context<ArchiveSnapshot>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+
+        // Only the admin can archive the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        // Totals are only final once the campaign no longer takes donations
+        if matches!(campaign.status, CampaignStatus::Active | CampaignStatus::Paused) {
+            return Err(ErrorCode::CampaignNotEnded.into());
+        }
+
+        ctx.accounts.archive.record(campaign.key(), campaign, Clock::get()?.unix_timestamp);
+
+        msg!("Campaign archived");
+        Ok(())
+    }
+
+    /// Closes a finished or unfunded campaign, sending its remaining lamports to the admin
+    ///
+    /// An ended campaign can be closed, and so can an active or paused one that never received
+    /// a donation, pledge or match. The `CampaignArchive` is created here unless
+    /// `archive_snapshot` already recorded it. Lamports still owed to someone block the close
+    /// rather than being swept, because the campaign account is the ledger they're claimed
+    /// against: unclaimed fees, quarantined donations, the sponsor's match pool, a successful
+    /// campaign's endowment, and refundable donations above `dust_threshold` of a cancelled or
+    /// failed campaign. Any such dust is swept to the admin along with the rent.
+    pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+
+        // Only the admin can close the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if campaign.disputed {
+            return Err(ErrorCode::CampaignDisputed.into());
+        }
+        // Closing would hand the admin fees owed to the recipients
+        if campaign.fees_accrued_total() > 0 {
+            return Err(ErrorCode::FeesUnclaimed.into());
+        }
+        if campaign.quarantined > 0 {
+            return Err(ErrorCode::QuarantinePending.into());
+        }
+        if campaign.match_pool > 0 {
+            return Err(ErrorCode::MatchPoolOutstanding.into());
+        }
+        match campaign.status {
+            CampaignStatus::Active | CampaignStatus::Paused => {
+                if !campaign.is_unfunded() {
+                    return Err(ErrorCode::CampaignNotEnded.into());
+                }
+            }
+            // A successful campaign's endowment is kept for good, so closing would hand it to the admin
+            CampaignStatus::Successful => {
+                if campaign.endowment_total > 0 {
+                    return Err(ErrorCode::EndowmentProtected.into());
+                }
+            }
+            // Closing would hand the admin lamports still owed to donors. The endowment of a campaign
+            // that didn't succeed doesn't hold up closing: refunds release it along with the rest,
+            // and without a refund policy nobody else can claim it, so it's swept with the balance
+            CampaignStatus::Cancelled | CampaignStatus::Failed => {
+                if campaign.refund_policy != RefundPolicy::None && campaign.net_raised() > campaign.dust_threshold {
+                    return Err(ErrorCode::RefundsOutstanding.into());
+                }
+            }
+        }
+
+        // The archive outlives the campaign, so close_aux reads the rent target from it. Snapshot
+        // the campaign unless it was archived already (an archive older than the campaign was
+        // left by an earlier campaign at the same address)
+        let archive = &mut ctx.accounts.archive;
+        if archive.campaign == Pubkey::default() || archive.archived_at < campaign.created_at {
+            archive.record(campaign.key(), campaign, Clock::get()?.unix_timestamp);
+        }
+        archive.rent_sweep_target = campaign.rent_sweep_target();
+
+        let lamports = campaign.to_account_info().lamports();
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::Close, ctx.accounts.user.key(), lamports)?;
+
+        msg!("Campaign closed");
+        Ok(())
+    }
+
+    /// Moves a campaign's entire available balance into another campaign
+    ///
+    /// Used when organizers merge efforts. Everything above the source's reserved lamports
+    /// is credited to the destination like a donation from the source campaign, recorded in
+    /// the source's donor record there.
+    pub fn transfer_to_campaign(ctx: Context<TransferToCampaign>) -> Result<()> {
+        let source = &ctx.accounts.source;
+        let destination = &ctx.accounts.destination;
+
+        // A campaign cannot be merged into itself
+        if source.key() == destination.key() {
+            return Err(ErrorCode::SameCampaign.into());
+        }
+
+        // Only the source campaign's admin can move its funds
+        if source.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        // The source's funds must be free to move, and only a live destination takes donations
+        if source.disputed {
+            return Err(ErrorCode::CampaignDisputed.into());
+        }
+        let now = Clock::get()?.unix_timestamp;
+        source.check_withdrawable(now, Clock::get()?.slot)?;
+        destination.check_accepting_donations(now, Clock::get()?.slot)?;
+        destination.check_donor_capacity(ctx.accounts.donor_record.donation_count == 0)?;
+
+        // Merging is an outflow too, so it honours the source's allowlist
+        source.check_destination(&destination.key())?;
+
+        let amount = source.spendable_lamports(source.to_account_info().lamports(), Clock::get()?.slot)?;
+        if amount == 0 {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+
+        // Both accounts are owned by this program, so lamports can be moved directly
+        move_lamports(&source.to_account_info(), &destination.to_account_info(), amount)?;
+
+        let source_key = source.key();
+        ctx.accounts.source.amount_withdrawn = ctx.accounts.source.amount_withdrawn.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        ctx.accounts.source.record_period_withdrawal(amount, now)?;
+
+        // The destination books the merge like a donation from the source campaign, so it can
+        // be refunded to the source if the destination fails or is cancelled
+        let destination_key = ctx.accounts.destination.key();
+        let destination = &mut ctx.accounts.destination;
+        let donor_hash = destination.donor_hash(&source_key);
+        let donor_record = &ctx.accounts.donor_record;
+        credit_donation(destination, donor_hash, amount, donor_record.hide_amount, donor_record.donation_count == 0, now, Clock::get()?.slot)?;
+        ctx.accounts.donor_record.record_donation(destination_key, source_key, donor_hash, amount, now, ctx.bumps.donor_record)?;
+        ctx.accounts.donor_record.weighted_score = ctx.accounts.donor_record.weighted_score.saturating_add(destination.time_weighted(amount, now));
+
+        emit!(FundsMerged {
+            source: source_key,
+            destination: destination_key,
+            amount,
+            new_total: if destination.total_hidden(now) { 0 } else { destination.amount_donated },
+            total_hidden: destination.total_hidden(now),
+            timestamp: now,
+        });
+
+        audit(&ctx.accounts.audit_log, source_key, AuditAction::MergeFunds, ctx.accounts.user.key(), amount)?;
+
+        msg!("Campaign funds merged");
+        Ok(())
+    }
+
+    /// Pledges to donate `amount` lamports to a campaign by `due_by`
+    ///
+    /// The pledge only counts toward `pledged_total`; it reaches `amount_donated` when the
+    /// donor funds it with `fulfill_pledge`. A donor has at most one open pledge per campaign.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the campaign, pledge and donor accounts
+    /// * `amount` - The promised amount in lamports, before fees
+    /// * `due_by` - Unix timestamp after which the pledge can be expired
+    pub fn pledge(ctx: Context<MakePledge>, amount: u64, due_by: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        ctx.accounts.campaign.check_accepting_donations(now, Clock::get()?.slot)?;
+        if amount == 0 || due_by <= now {
+            return Err(ErrorCode::InvalidPledge.into());
+        }
+        ctx.accounts.campaign.check_donation_step(amount)?;
+        // A flagged wallet's promise would inflate `pledged_total` with money that can't be credited
+        if ctx.accounts.campaign.check_denylist(ctx.accounts.denylist_entry.as_ref().map(|e| e.as_ref()))? {
+            return Err(ErrorCode::DonorFlagged.into());
+        }
+
+        let pledge = &mut ctx.accounts.pledge;
+        pledge.campaign = ctx.accounts.campaign.key();
+        pledge.donor = ctx.accounts.user.key();
+        pledge.amount = amount;
+        pledge.due_by = due_by;
+        pledge.created_at = now;
+        pledge.bump = ctx.bumps.pledge;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.pledged_total = campaign.pledged_total.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        msg!("Pledge created");
+        Ok(())
+    }
+
+    /// Funds an open pledge, donating its amount and closing the pledge
+    ///
+    /// The donation goes through the same fee split, matching, deadline extension and donor
+    /// bookkeeping as `donate`.
+    pub fn fulfill_pledge(ctx: Context<FulfillPledge>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pledged = ctx.accounts.pledge.amount;
+
+        ctx.accounts.campaign.check_accepting_donations(now, Clock::get()?.slot)?;
+        ctx.accounts.campaign.check_attestation(ctx.accounts.attestation.as_deref(), &ctx.accounts.user.key(), now)?;
+        ctx.accounts.campaign.check_donor_capacity(ctx.accounts.donor_record.donation_count == 0)?;
+        if now > ctx.accounts.pledge.due_by {
+            return Err(ErrorCode::PledgeExpired.into());
+        }
+
+        // A wallet flagged since pledging is screened like in `donate`: the money is held, not credited
+        if ctx.accounts.campaign.check_denylist(ctx.accounts.denylist_entry.as_ref().map(|e| e.as_ref()))? {
+            let quarantine = ctx.accounts.quarantine.as_mut().ok_or(ErrorCode::QuarantineRequired)?;
+            quarantine_donation(
+                &mut ctx.accounts.campaign,
+                &mut ctx.accounts.donor_record,
+                quarantine,
+                &ctx.accounts.user.to_account_info(),
+                pledged,
+                now,
+                (ctx.bumps.donor_record, ctx.bumps.quarantine.unwrap_or_default()),
+            )?;
+            let campaign = &mut ctx.accounts.campaign;
+            campaign.pledged_total = campaign.pledged_total.checked_sub(pledged).ok_or(ErrorCode::Overflow)?;
+
+            msg!("Pledge fulfillment quarantined");
+            return Ok(());
+        }
+
+        let fee_accounts = [&ctx.accounts.fee_recipient_a, &ctx.accounts.fee_recipient_b];
+        let amount = pay_fees(&mut ctx.accounts.campaign, &ctx.accounts.user.to_account_info(), fee_accounts, pledged)?;
+        transfer_lamports(&ctx.accounts.user.to_account_info(), &ctx.accounts.campaign.to_account_info(), amount)?;
+
+        // The promise turns into a donation
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.pledged_total = campaign.pledged_total.checked_sub(pledged).ok_or(ErrorCode::Overflow)?;
+        let campaign_key = campaign.key();
+        let donor_key = ctx.accounts.user.key();
+        let donor_hash = campaign.donor_hash(&donor_key);
+        let donor_record = &ctx.accounts.donor_record;
+        credit_donation(campaign, donor_hash, amount, donor_record.hide_amount, donor_record.donation_count == 0, now, Clock::get()?.slot)?;
+
+        ctx.accounts.donor_record.record_donation(campaign_key, donor_key, donor_hash, amount, now, ctx.bumps.donor_record)?;
+        ctx.accounts.donor_record.weighted_score = ctx.accounts.donor_record.weighted_score.saturating_add(campaign.time_weighted(amount, now));
+
+        emit_donation(campaign, donor_hash, amount, ctx.accounts.donor_record.hide_amount, now);
+
+        msg!("Pledge fulfilled");
+        Ok(())
+    }
+
+    /// Expires an unfulfilled pledge after its due date, returning its rent to the donor
+    ///
+    /// Permissionless, so keepers can clean up `pledged_total`.
+    pub fn expire_pledge(ctx: Context<ExpirePledge>) -> Result<()> {
+        let pledge = &ctx.accounts.pledge;
+        if Clock::get()?.unix_timestamp <= pledge.due_by {
+            return Err(ErrorCode::PledgeNotExpired.into());
+        }
+
+        ctx.accounts.campaign.pledged_total = ctx.accounts.campaign.pledged_total.checked_sub(pledge.amount).ok_or(ErrorCode::Overflow)?;
+
+        msg!("Pledge expired");
+        Ok(())
+    }
+
+    /// Sets whether the donor's amounts are hidden in public donation events
+    ///
+    /// Campaign totals still count every donation; only the per-donation figure is withheld.
+    /// Can be set before the first donation, in which case the donor record is created here.
+    pub fn set_amount_visibility(ctx: Context<SetAmountVisibility>, hide_amount: bool) -> Result<()> {
+        let campaign_key = ctx.accounts.campaign.key();
+        let donor_key = ctx.accounts.user.key();
+        let donor_record = &mut ctx.accounts.donor_record;
+        donor_record.campaign = campaign_key;
+        donor_record.donor = donor_key;
+        donor_record.donor_hash = ctx.accounts.campaign.donor_hash(&donor_key);
+        donor_record.bump = ctx.bumps.donor_record;
+        donor_record.hide_amount = hide_amount;
+
+        msg!("Donation visibility updated");
+        Ok(())
+    }
+
+    /// Replaces the campaign's name, description and/or thank-you message
+    ///
+    /// The text fields are fixed-size, so a shorter value zeroes the bytes it no longer
+    /// uses; the account data stays deterministic for hashing and archival.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the campaign and admin accounts
+    /// * `name` - The new name, or `None` to keep the current one
+    /// * `description` - The new description, or `None` to keep the current one
+    /// * `thank_you` - The new thank-you message, or `None` to keep the current one
+    pub fn update_metadata(
+        ctx: Context<UpdateCampaign>,
+        name: Option<String>,
+        description: Option<String>,
+        thank_you: Option<String>,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can edit the campaign's metadata
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        if let Some(name) = name {
+            campaign.set_name(&name)?;
+        }
+        if let Some(description) = description {
+            campaign.set_description(&description)?;
+        }
+        if let Some(thank_you) = thank_you {
+            campaign.set_thank_you(&thank_you)?;
+        }
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::UpdateMetadata, ctx.accounts.user.key(), 0)?;
+
+        msg!("Campaign metadata updated");
+        Ok(())
+    }
+
+    /// Refunds a donor of a failed all-or-nothing campaign on their behalf
+    ///
+    /// Permissionless, so keepers can wind down failed campaigns without every donor
+    /// calling `refund`. The donor's whole contribution is returned, less the campaign's
+    /// crank bounty which goes to the cranker, and the donor record is closed.
+    pub fn crank_refund(ctx: Context<CrankRefund>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Only all-or-nothing campaigns that missed their goal are wound down this way
+        if !campaign.is_all_or_nothing() || campaign.is_successful() {
+            return Err(ErrorCode::RefundNotAvailable.into());
+        }
+        campaign.check_refund_allowed(now, Clock::get()?.slot)?;
+
+        let amount = ctx.accounts.donor_record.amount_donated;
+        let available = campaign.available_lamports(campaign.to_account_info().lamports())?;
+        if amount == 0 || available < amount {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+        let bounty = bps::apply(amount, campaign.crank_bounty_bps).ok_or(ErrorCode::InvalidCrankBounty)?;
+
+        let to_donor = amount.checked_sub(bounty).ok_or(ErrorCode::Overflow)?;
+        move_lamports(&campaign.to_account_info(), &ctx.accounts.donor.to_account_info(), to_donor)?;
+        move_lamports(&campaign.to_account_info(), &ctx.accounts.cranker.to_account_info(), bounty)?;
+        campaign.record_refund(amount)?;
+        campaign.donor_count = campaign.donor_count.saturating_sub(1); // Migrated campaigns start counting at 0
+
+        if campaign.recompute_status(now, Clock::get()?.slot) {
+            emit_finalized(campaign, now);
+        }
+
+        msg!("Refund cranked");
+        Ok(())
+    }
+
+    /// Issues (or renews) an attestation that `subject` passed the issuer's verification
+    ///
+    /// The signer is the issuer; campaigns that name it as `attestation_issuer` admit the subject.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the attestation and issuer accounts
+    /// * `subject` - The wallet being attested
+    /// * `expires_at` - Unix timestamp the attestation stops being valid (0 = never)
+    pub fn issue_attestation(ctx: Context<IssueAttestation>, subject: Pubkey, expires_at: i64) -> Result<()> {
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.issuer = ctx.accounts.issuer.key();
+        attestation.subject = subject;
+        attestation.issued_at = Clock::get()?.unix_timestamp;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.attestation;
+
+        msg!("Attestation issued");
+        Ok(())
+    }
+
+    /// Revokes an attestation, returning its rent to the issuer
+    pub fn revoke_attestation(_ctx: Context<RevokeAttestation>) -> Result<()> {
+        msg!("Attestation revoked");
+        Ok(())
+    }
+
+    /// Changes whether donors need an attestation, and from which issuer
+    pub fn set_attestation(ctx: Context<UpdateCampaign>, require_attestation: bool, attestation_issuer: Pubkey) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can change who may donate
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if require_attestation && attestation_issuer == Pubkey::default() {
+            return Err(ErrorCode::InvalidAttestationIssuer.into());
+        }
+
+        campaign.require_attestation = require_attestation;
+        campaign.attestation_issuer = attestation_issuer;
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::SetAttestation, ctx.accounts.user.key(), 0)?;
+
+        msg!("Attestation requirement updated");
+        Ok(())
+    }
+
+    /// Checks the campaign's balance against its bookkeeping and emits a `Reconciliation` event
+    ///
+    /// Read-only and permissionless. See `Campaign::expected_balance` for the invariant; lamports
+    /// sent to the account outside the program (e.g. a plain transfer) show up as positive drift.
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let expected = campaign.expected_balance();
+        let actual = campaign.to_account_info().lamports();
+        let drift = i128::from(actual) - expected;
+
+        emit!(Reconciliation {
+            campaign: campaign.key(),
+            balanced: drift == 0,
+            expected_balance: u64::try_from(expected.max(0)).unwrap_or(u64::MAX),
+            actual_balance: actual,
+            drift: drift.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Campaign reconciled");
+        Ok(())
+    }
+
+    /// Creates the program config, making the signer its authority
+    ///
+    /// Only the program's upgrade authority can initialize the config, so it can't be front-run.
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.bump = ctx.bumps.config;
+        config.fee_bps = 0;
+        config.treasury = ctx.accounts.authority.key();
+
+        msg!("Program config initialized");
+        Ok(())
+    }
+
+    /// Sets the platform fee taken from every withdrawal and the treasury it goes to
+    ///
+    /// Campaigns made fee-exempt with `set_campaign_fee_exempt`, and campaigns with a withdrawal
+    /// allowlist, don't pay it.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the config and its authority
+    /// * `fee_bps` - Fee in basis points, at most `MAX_PLATFORM_FEE_BPS`
+    /// * `treasury` - Account receiving the fee
+    pub fn set_platform_fee(ctx: Context<SetPlatformFee>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+        if fee_bps > MAX_PLATFORM_FEE_BPS {
+            return Err(ErrorCode::InvalidFeeBps.into());
+        }
+        if treasury == Pubkey::default() {
+            return Err(ErrorCode::InvalidTreasury.into());
+        }
+
+        let config = &mut ctx.accounts.config;
+        config.fee_bps = fee_bps;
+        config.treasury = treasury;
+
+        emit!(PlatformFeeChanged {
+            fee_bps,
+            treasury,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Platform fee updated");
+        Ok(())
+    }
+
+    /// Puts a campaign under dispute; only the config authority can raise one
+    ///
+    /// Until `resolve_dispute`, withdrawals go to the campaign's `DisputeEscrow` and the
+    /// campaign's funds can't be moved anywhere else.
+    pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        if campaign.disputed {
+            return Err(ErrorCode::CampaignDisputed.into());
+        }
+
+        campaign.disputed = true;
+
+        emit!(DisputeRaised {
+            campaign: campaign.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Dispute raised");
+        Ok(())
+    }
+
+    /// Resolves a campaign's dispute, releasing the escrowed funds
+    ///
+    /// With `to_admin`, the escrow is paid out to the campaign's admin. Otherwise the funds
+    /// return to the campaign, which is cancelled (without a reopen window) whatever its status,
+    /// so donors can claim refunds; a keep-it-all campaign is switched to refunds on failure
+    /// for this. Either way the escrow is closed and its rent goes back to the admin who paid it.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, to_admin: bool) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let escrow = &mut ctx.accounts.dispute_escrow;
+        let now = Clock::get()?.unix_timestamp;
+
+        if !campaign.disputed {
+            return Err(ErrorCode::NotDisputed.into());
+        }
+
+        let amount = escrow.amount;
+        escrow.amount = 0;
+        if to_admin {
+            move_lamports(&escrow.to_account_info(), &ctx.accounts.admin.to_account_info(), amount)?;
+        } else {
+            move_lamports(&escrow.to_account_info(), &campaign.to_account_info(), amount)?;
+            campaign.amount_withdrawn = campaign.amount_withdrawn.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+            // Even a finalized campaign is cancelled, since only cancelling opens refunds once it succeeded
+            if campaign.status != CampaignStatus::Cancelled {
+                campaign.status = CampaignStatus::Cancelled;
+                campaign.cancelled_at = now;
+            }
+            campaign.reopen_until = now;
+            if campaign.refund_policy == RefundPolicy::None {
+                campaign.refund_policy = RefundPolicy::OnFailure;
+            }
+        }
+        campaign.disputed = false;
+
+        emit!(DisputeResolved {
+            campaign: campaign.key(),
+            amount,
+            to_admin,
+            timestamp: now,
+        });
+
+        msg!("Dispute resolved");
+        Ok(())
+    }
+
+    /// Emits a `CampaignConfig` event with every configurable setting of the campaign
+    ///
+    /// Read-only and permissionless, so clients can display a campaign's settings without
+    /// decoding the whole account. New settings are added to the event as they are introduced.
+    pub fn config_info(ctx: Context<ConfigInfo>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+
+        emit!(CampaignConfig {
+            campaign: campaign.key(),
+            admin: campaign.admin,
+            fee_bps: campaign.fee_bps,
+            fee_recipients: campaign.fee_recipients,
+            goal: campaign.goal,
+            goal_type: campaign.goal_type,
+            hard_goal: campaign.hard_goal,
+            deadline: campaign.deadline,
+            min_seconds_between_donations: campaign.min_seconds_between_donations,
+            refund_policy: campaign.refund_policy,
+            refund_delay: campaign.refund_delay,
+            reopen_grace_period: campaign.reopen_grace_period,
+            allowed_destinations: campaign.allowed_destinations[..usize::from(campaign.allowed_destination_count)].to_vec(),
+            verification_min: campaign.verification_min,
+            currency_symbol: campaign.currency_symbol().to_string(),
+            crank_bounty_bps: campaign.crank_bounty_bps,
+            min_donors: campaign.min_donors,
+            max_donors: campaign.max_donors,
+            require_attestation: campaign.require_attestation,
+            attestation_issuer: campaign.attestation_issuer,
+            dust_threshold: campaign.dust_threshold,
+            withdraw_cliff: campaign.withdraw_cliff,
+            accrue_fees: campaign.accrue_fees,
+            hide_total_until_deadline: campaign.hide_total_until_deadline,
+            listed: campaign.listed,
+            event_level: campaign.event_level,
+            donation_maturity_slots: campaign.donation_maturity_slots,
+            anti_snipe_window: campaign.anti_snipe_window,
+            anti_snipe_extension: campaign.anti_snipe_extension,
+            withdraw_condition: campaign.withdraw_condition,
+            withdraw_condition_owner: campaign.withdraw_condition_owner,
+            endowment_bps: campaign.endowment_bps,
+            denylist: campaign.denylist,
+            donation_step: campaign.donation_step,
+            fee_exempt: campaign.fee_exempt_campaign,
+            deadline_mode: campaign.deadline_mode,
+            deadline_slot: campaign.deadline_slot,
+            rent_sweep_target: campaign.rent_sweep_target(),
+            locale: campaign.locale,
+            match_expiry: campaign.match_expiry,
+            max_withdraw_pct_per_period: campaign.max_withdraw_pct_per_period,
+            withdraw_period: campaign.withdraw_period,
+        });
+
+        msg!("Campaign config emitted");
+        Ok(())
+    }
+
+    /// Challenges another campaign to a head-to-head, pending its admin's acceptance
+    ///
+    /// No funds ever move between rivals; the link only lets clients compare their totals.
+    /// Proposing the default pubkey withdraws a pending challenge.
+    ///
+    /// # Arguments
+    /// * `rival` - Address of the campaign being challenged
+    pub fn set_rival(ctx: Context<UpdateCampaign>, rival: Pubkey) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can challenge on the campaign's behalf
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if rival == campaign.key() {
+            return Err(ErrorCode::SelfRivalry.into());
+        }
+
+        campaign.proposed_rival = rival;
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::ProposeRival, ctx.accounts.user.key(), 0)?;
+
+        msg!("Rival proposed");
+        Ok(())
+    }
+
+    /// Accepts a challenge from `challenger`, linking the two campaigns as rivals
+    pub fn accept_rival(ctx: Context<AcceptRival>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let challenger = &mut ctx.accounts.challenger;
+
+        // Only the challenged campaign's admin can agree to the rivalry
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if campaign.key() == challenger.key() {
+            return Err(ErrorCode::SelfRivalry.into());
+        }
+        if challenger.proposed_rival != campaign.key() {
+            return Err(ErrorCode::RivalNotProposed.into());
+        }
+
+        challenger.proposed_rival = Pubkey::default();
+        challenger.rival = campaign.key();
+        campaign.rival = challenger.key();
+
+        let now = Clock::get()?.unix_timestamp;
+        emit!(RivalryLinked {
+            campaign: challenger.key(),
+            rival: campaign.key(),
+            campaign_raised: if challenger.total_hidden(now) { 0 } else { challenger.net_raised() },
+            rival_raised: if campaign.total_hidden(now) { 0 } else { campaign.net_raised() },
+            timestamp: now,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::AcceptRival, ctx.accounts.user.key(), 0)?;
+
+        msg!("Rivalry accepted");
+        Ok(())
+    }
+
+    /// Emits a `RivalryStandings` event comparing a campaign's net raised with its rival's
+    ///
+    /// Read-only and permissionless. Unavailable while either campaign hides its total.
+    pub fn compare_rivals(ctx: Context<CompareRivals>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let rival = &ctx.accounts.rival;
+
+        if campaign.rival != rival.key() {
+            return Err(ErrorCode::NotRivals.into());
+        }
+        let now = Clock::get()?.unix_timestamp;
+        if campaign.total_hidden(now) || rival.total_hidden(now) {
+            return Err(ErrorCode::TotalHidden.into());
+        }
+
+        let campaign_raised = campaign.net_raised();
+        let rival_raised = rival.net_raised();
+        emit!(RivalryStandings {
+            campaign: campaign.key(),
+            rival: rival.key(),
+            campaign_raised,
+            rival_raised,
+            leader: match campaign_raised.cmp(&rival_raised) {
+                std::cmp::Ordering::Greater => campaign.key(),
+                std::cmp::Ordering::Less => rival.key(),
+                std::cmp::Ordering::Equal => Pubkey::default(),
+            },
+            timestamp: now,
+        });
+
+        msg!("Rivals compared");
+        Ok(())
+    }
+
+    /// Pays a fee recipient the fees the campaign has accrued for it
+    ///
+    /// Only the recipient itself can claim. Fees of every donation since the last claim are paid
+    /// in one transfer.
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let recipient = &ctx.accounts.recipient;
+
+        let index = campaign
+            .fee_recipients
+            .iter()
+            .zip(campaign.fees_accrued)
+            .position(|(fee_recipient, accrued)| fee_recipient.recipient == recipient.key() && accrued > 0)
+            .ok_or(ErrorCode::NoFeesAccrued)?;
+        let amount = campaign.fees_accrued[index];
+        campaign.fees_accrued[index] = 0;
+
+        // The campaign is owned by this program, so lamports can be moved directly
+        move_lamports(&campaign.to_account_info(), &recipient.to_account_info(), amount)?;
+
+        emit!(FeesClaimed {
+            campaign: campaign.key(),
+            recipient: recipient.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Fees claimed");
+        Ok(())
+    }
+
+    /// Posts a donor's message to the campaign's comment feed
+    ///
+    /// Messages are keyed by the campaign's `message_count`, so every message is kept and the
+    /// feed can be read back in order by sequence number.
+    ///
+    /// # Arguments
+    /// * `text` - The message, at most `MAX_MESSAGE_LEN` bytes
+    pub fn post_message(ctx: Context<PostMessage>, text: String) -> Result<()> {
+        if ctx.accounts.donor_record.donation_count == 0 {
+            return Err(ErrorCode::NotADonor.into());
+        }
+
+        let campaign = &mut ctx.accounts.campaign;
+        let message = &mut ctx.accounts.message;
+        message.text_len = croudfunding_types::write_padded(&mut message.text, &text).ok_or(ErrorCode::MessageTooLong)?;
+        message.campaign = campaign.key();
+        message.author = ctx.accounts.author.key();
+        message.sequence = campaign.message_count;
+        message.posted_at = Clock::get()?.unix_timestamp;
+        message.bump = ctx.bumps.message;
+        campaign.message_count = campaign.message_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        msg!("Message posted");
+        Ok(())
+    }
+
+    /// Lists or unlists the campaign in public feeds
+    ///
+    /// Unlisted campaigns keep accepting donations; indexers use the `ListingChanged` event to
+    /// leave them out of public listings.
+    pub fn set_listed(ctx: Context<UpdateCampaign>, listed: bool) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can change where the campaign is shown
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        campaign.listed = listed;
+
+        emit!(ListingChanged {
+            campaign: campaign.key(),
+            listed,
+            locale: campaign.locale,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::SetListed, ctx.accounts.user.key(), 0)?;
+
+        msg!("Campaign listing updated");
+        Ok(())
+    }
+
+    /// Changes which event each donation emits
+    pub fn set_event_level(ctx: Context<UpdateCampaign>, event_level: EventLevel) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can tune the campaign's logging
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        campaign.event_level = event_level;
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::SetEventLevel, ctx.accounts.user.key(), 0)?;
+
+        msg!("Event level updated");
+        Ok(())
+    }
+
+    /// Closes auxiliary accounts left behind by a closed campaign, sending their rent to the
+    /// campaign's rent sweep target (the admin unless configured otherwise)
+    ///
+    /// The accounts go in `remaining_accounts`. Each must be a donor record, follow,
+    /// organization total, pledge, donation message or audit log PDA derived from this
+    /// campaign's address; anything else is rejected.
+    pub fn close_aux<'info>(ctx: Context<'_, '_, 'info, 'info, CloseAux<'info>>) -> Result<()> {
+        // Auxiliary accounts are only orphaned once the campaign itself is gone
+        if ctx.accounts.campaign.owner == ctx.program_id {
+            return Err(ErrorCode::CampaignNotClosed.into());
+        }
+
+        let campaign = ctx.accounts.campaign.key();
+        let target = ctx.accounts.rent_sweep_target.to_account_info();
+        for info in ctx.remaining_accounts {
+            let discriminator: [u8; DISCRIMINATOR_LEN] = info
+                .try_borrow_data()?
+                .get(..DISCRIMINATOR_LEN)
+                .and_then(|d| d.try_into().ok())
+                .ok_or(ErrorCode::AuxAccountMismatch)?;
+            match discriminator {
+                DONOR_RECORD_DISCRIMINATOR => {
+                    let record = Account::<DonorRecord>::try_from(info)?;
+                    let seeds: &[&[u8]] = &[DONOR_SEED, campaign.as_ref(), record.donor.as_ref(), &[record.bump]];
+                    close_aux_account(&record, seeds, &target)?;
+                }
+                FOLLOW_DISCRIMINATOR => {
+                    let follow = Account::<Follow>::try_from(info)?;
+                    let seeds: &[&[u8]] = &[FOLLOW_SEED, follow.user.as_ref(), campaign.as_ref(), &[follow.bump]];
+                    close_aux_account(&follow, seeds, &target)?;
+                }
+                ORG_DONATION_DISCRIMINATOR => {
+                    let org_donation = Account::<OrgDonation>::try_from(info)?;
+                    let seeds: &[&[u8]] = &[ORG_SEED, campaign.as_ref(), org_donation.org.as_ref(), &[org_donation.bump]];
+                    close_aux_account(&org_donation, seeds, &target)?;
+                }
+                PLEDGE_DISCRIMINATOR => {
+                    let pledge = Account::<Pledge>::try_from(info)?;
+                    let seeds: &[&[u8]] = &[PLEDGE_SEED, campaign.as_ref(), pledge.donor.as_ref(), &[pledge.bump]];
+                    close_aux_account(&pledge, seeds, &target)?;
+                }
+                DONATION_MESSAGE_DISCRIMINATOR => {
+                    let message = Account::<DonationMessage>::try_from(info)?;
+                    let sequence = message.sequence.to_le_bytes();
+                    let seeds: &[&[u8]] = &[MESSAGE_SEED, campaign.as_ref(), &sequence, &[message.bump]];
+                    close_aux_account(&message, seeds, &target)?;
+                }
+                AUDIT_LOG_DISCRIMINATOR => {
+                    let audit_log = AccountLoader::<AuditLog>::try_from(info)?;
+                    let (expected, _) = Pubkey::find_program_address(&[AUDIT_LOG_SEED, campaign.as_ref()], ctx.program_id);
+                    if audit_log.key() != expected {
+                        return Err(ErrorCode::AuxAccountMismatch.into());
+                    }
+                    audit_log.close(target.clone())?;
+                }
+                _ => return Err(ErrorCode::AuxAccountMismatch.into()),
+            }
+        }
+
+        msg!("Auxiliary accounts closed");
+        Ok(())
+    }
+
+    /// Permanently removes the campaign's admin
+    ///
+    /// The admin is set to the default pubkey, which nobody can sign for, so every admin-gated
+    /// instruction (withdrawals included) fails from then on. Irreversible, hence the explicit
+    /// confirmation byte.
+    ///
+    /// # Arguments
+    /// * `confirmation` - Must be `RENOUNCE_CONFIRMATION`
+    pub fn renounce_admin(ctx: Context<UpdateCampaign>, confirmation: u8) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can give up the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if confirmation != RENOUNCE_CONFIRMATION {
+            return Err(ErrorCode::RenounceNotConfirmed.into());
+        }
+
+        campaign.admin = Pubkey::default();
+        campaign.pending_admin = Pubkey::default();
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::RenounceAdmin, ctx.accounts.user.key(), 0)?;
+
+        msg!("Campaign admin renounced");
+        Ok(())
+    }
+
+    /// Proposes a new admin for the campaign, who takes over once they call `accept_admin`
+    ///
+    /// The current admin keeps full control until then, and can withdraw the proposal by
+    /// proposing the default pubkey or replace it with another one.
+    ///
+    /// # Arguments
+    /// * `new_admin` - The proposed admin
+    pub fn transfer_admin(ctx: Context<UpdateCampaign>, new_admin: Pubkey) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can hand over the campaign
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        campaign.pending_admin = new_admin;
+
+        emit!(AdminTransferProposed {
+            campaign: campaign.key(),
+            admin: campaign.admin,
+            pending_admin: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::TransferAdmin, ctx.accounts.user.key(), 0)?;
+
+        msg!("Admin transfer proposed");
+        Ok(())
+    }
+
+    /// Makes the signer the campaign's admin; the signer must be the `pending_admin`
+    ///
+    /// Takes effect immediately: from here on only the new admin can withdraw or change the campaign.
+    pub fn accept_admin(ctx: Context<UpdateCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let new_admin = ctx.accounts.user.key();
+
+        if campaign.pending_admin == Pubkey::default() || campaign.pending_admin != new_admin {
+            return Err(ErrorCode::NotPendingAdmin.into());
+        }
+
+        let previous_admin = campaign.admin;
+        campaign.admin = new_admin;
+        campaign.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferred {
+            campaign: campaign.key(),
+            previous_admin,
+            admin: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::AcceptAdmin, new_admin, 0)?;
+
+        msg!("Admin transfer accepted");
+        Ok(())
+    }
+
+    /// Emits the campaign PDA and bump `create` derives for `creator` and `idempotency_key`
+    ///
+    /// Read-only and accountless, so SDKs can check their derivation against the program's seeds.
+    pub fn derive_campaign(ctx: Context<DeriveCampaign>, creator: Pubkey, idempotency_key: [u8; 16]) -> Result<()> {
+        let (campaign, bump) = Pubkey::find_program_address(
+            &[CAMPAIGN_SEED, creator.as_ref(), croudfunding_types::campaign_seed_key(&idempotency_key)],
+            ctx.program_id,
+        );
+
+        emit!(CampaignDerived {
+            creator,
+            idempotency_key,
+            campaign,
+            bump,
+        });
+
+        msg!("Campaign address derived");
+        Ok(())
+    }
+
+    /// Credits lamports sent straight to the campaign account to `amount_donated`
+    ///
+    /// Only the positive drift reported by `reconcile` is credited, as anonymous donations,
+    /// so the counter can only move up to the actual balance and never be set arbitrarily.
+    /// The deposit is booked through `Campaign::record_donation`, so endowment, maturity and
+    /// matching apply, but without a donor record; a campaign with a refund policy is refused,
+    /// since nobody could claim the deposit back and it would hold up closing.
+    /// While the campaign hides its total, `DonationsSynced` doesn't publish the new one.
+    pub fn sync_donated(ctx: Context<UpdateCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can change the campaign's totals
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+
+        // A finalized campaign's totals are settled, so syncing counts as taking a donation
+        let now = Clock::get()?.unix_timestamp;
+        campaign.check_accepting_donations(now, Clock::get()?.slot)?;
+        if campaign.refund_policy != RefundPolicy::None {
+            return Err(ErrorCode::SyncRefundable.into());
+        }
+
+        let drift = i128::from(campaign.to_account_info().lamports()) - campaign.expected_balance();
+        let amount = u64::try_from(drift).ok().filter(|amount| *amount > 0).ok_or(ErrorCode::NothingToSync)?;
+        let hard_goal_was_reached = campaign.hard_goal_reached();
+        campaign.record_donation(amount, false, Clock::get()?.slot)?;
+        if !hard_goal_was_reached {
+            emit_hard_goal(campaign, now);
+        }
+
+        let total_hidden = campaign.total_hidden(now);
+        emit!(DonationsSynced {
+            campaign: campaign.key(),
+            amount,
+            new_total: if total_hidden { 0 } else { campaign.amount_donated },
+            total_hidden,
+            timestamp: now,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::SyncDonated, ctx.accounts.user.key(), amount)?;
+
+        msg!("Untracked deposits synced");
+        Ok(())
+    }
+
+    /// Flags `subject` on the signer's denylist
+    ///
+    /// Campaigns that name the signer as their `denylist` quarantine the subject's donations.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the denylist entry and authority accounts
+    /// * `subject` - The wallet being flagged
+    pub fn flag_address(ctx: Context<FlagAddress>, subject: Pubkey) -> Result<()> {
+        let entry = &mut ctx.accounts.entry;
+        entry.authority = ctx.accounts.authority.key();
+        entry.subject = subject;
+        entry.flagged_at = Clock::get()?.unix_timestamp;
+        entry.bump = ctx.bumps.entry;
+
+        msg!("Address flagged");
+        Ok(())
+    }
+
+    /// Removes a wallet from the signer's denylist, returning the entry's rent to the authority
+    pub fn unflag_address(_ctx: Context<UnflagAddress>) -> Result<()> {
+        msg!("Address unflagged");
+        Ok(())
+    }
+
+    /// Releases a flagged donor's quarantined donations
+    ///
+    /// With `accept`, the funds are credited to the campaign as a regular donation from the
+    /// donor (no fee is taken), provided the campaign still takes donations. Otherwise they are
+    /// refunded to the donor, so a finalized or cancelled campaign's totals can't move (accepting
+    /// on a paused campaign fails until it resumes). Either way the quarantine account is closed
+    /// and its rent returned to the donor.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the config, campaign, quarantine and donor accounts
+    /// * `accept` - Whether to credit the funds to the campaign rather than refund them
+    pub fn release_quarantine(ctx: Context<ReleaseQuarantine>, accept: bool) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let donor_record = &mut ctx.accounts.donor_record;
+        let amount = ctx.accounts.quarantine.amount;
+        let donor_key = ctx.accounts.donor.key();
+        let now = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+
+        // Only a campaign still taking donations can be credited; the rest go back to the donor,
+        // except that a paused campaign may resume, so accepting waits for it
+        if accept && campaign.status == CampaignStatus::Paused {
+            return Err(ErrorCode::CampaignPaused.into());
+        }
+        let accept = accept && campaign.donation_block_reason(now, slot).is_none();
+        campaign.quarantined = campaign.quarantined.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        if accept {
+            let donor_hash = campaign.donor_hash(&donor_key);
+            credit_donation(campaign, donor_hash, amount, donor_record.hide_amount, donor_record.donation_count == 0, now, slot)?;
+            let bump = donor_record.bump;
+            donor_record.record_donation(campaign.key(), donor_key, donor_hash, amount, now, bump)?;
+        } else {
+            move_lamports(&campaign.to_account_info(), &ctx.accounts.donor.to_account_info(), amount)?;
+        }
+
+        emit!(QuarantineReleased {
+            campaign: campaign.key(),
+            donor: donor_key,
+            amount,
+            accepted: accept,
+            timestamp: now,
+        });
+
+        msg!("Quarantine released");
+        Ok(())
+    }
+
+    /// Merges a donor's record into the record of their new wallet
+    ///
+    /// Both wallets sign. The old record's totals are added to the new one (created here if
+    /// needed), and the old record is closed with its rent returned to the old wallet.
+    /// Merging can lower `donor_count`, so it's refused once the campaign is finalized or
+    /// cancelled, and when it would drop an all-or-nothing campaign below its success conditions.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the campaign, both donor records and both wallets
+    pub fn merge_donations(ctx: Context<MergeDonations>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let old_record = &ctx.accounts.old_record;
+        let new_record = &mut ctx.accounts.new_record;
+        let new_donor = ctx.accounts.new_donor.key();
+
+        // A settled campaign's donor count decided its outcome, so it can't change anymore
+        if !matches!(campaign.status, CampaignStatus::Active | CampaignStatus::Paused) {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+
+        // Two counted donors become one
+        if old_record.donation_count > 0 && new_record.donation_count > 0 {
+            let was_successful = campaign.is_successful();
+            campaign.donor_count = campaign.donor_count.saturating_sub(1);
+            // Losing a donor must not lock up or reopen refunds on an all-or-nothing campaign that made it
+            if campaign.is_all_or_nothing() && was_successful && !campaign.is_successful() {
+                return Err(ErrorCode::MergeBreaksGoal.into());
+            }
+        }
+        if new_record.donation_count == 0 {
+            new_record.hide_amount = old_record.hide_amount;
+        }
+
+        new_record.campaign = campaign.key();
+        new_record.donor = new_donor;
+        new_record.donor_hash = campaign.donor_hash(&new_donor);
+        new_record.bump = ctx.bumps.new_record;
+        new_record.amount_donated = new_record.amount_donated.checked_add(old_record.amount_donated).ok_or(ErrorCode::Overflow)?;
+        new_record.donation_count = new_record.donation_count.saturating_add(old_record.donation_count);
+        new_record.weighted_score = new_record.weighted_score.saturating_add(old_record.weighted_score);
+        new_record.last_donated_at = new_record.last_donated_at.max(old_record.last_donated_at);
+
+        emit!(DonationsMerged {
+            campaign: campaign.key(),
+            old_donor_hash: old_record.donor_hash,
+            new_donor_hash: new_record.donor_hash,
+            amount: old_record.amount_donated,
+            donor_total: new_record.amount_donated,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Donations merged");
+        Ok(())
+    }
+
+    /// Waives (or reinstates) the platform fee on a campaign, e.g. for a verified nonprofit
+    ///
+    /// Only the config authority can change this; it overrides the campaign's own fee settings.
+    /// An exempt campaign pays neither its donation fee nor the platform fee on withdrawals.
+    pub fn set_campaign_fee_exempt(ctx: Context<SetCampaignFeeExempt>, fee_exempt: bool) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.fee_exempt_campaign = fee_exempt;
+
+        emit!(FeeExemptionChanged {
+            campaign: campaign.key(),
+            fee_exempt,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::SetFeeExempt, ctx.accounts.authority.key(), 0)?;
+
+        msg!("Fee exemption updated");
+        Ok(())
+    }
+
+    /// Changes where close_aux sends the rent of the campaign's auxiliary accounts
+    ///
+    /// # Arguments
+    /// * `rent_sweep_target` - The receiving wallet, or the default pubkey to send it to the admin
+    pub fn set_rent_sweep_target(ctx: Context<UpdateCampaign>, rent_sweep_target: Pubkey) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        // Only the admin can redirect reclaimed rent
+        if campaign.admin != ctx.accounts.user.key() {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        if rent_sweep_target == campaign.key() {
+            return Err(ErrorCode::InvalidRentSweepTarget.into());
+        }
+
+        campaign.rent_sweep_target = rent_sweep_target;
+
+        audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::SetRentSweepTarget, ctx.accounts.user.key(), 0)?;
+
+        msg!("Rent sweep target updated");
+        Ok(())
+    }
+
+    /// Emits a `PreviewResult` showing what `donate` would do with a gross `amount`, without moving funds
+    ///
+    /// Runs the same checks, fee calculation and bookkeeping as `donate` against a copy of
+    /// the campaign, so the preview can't drift from the real thing. Pass the donor's record,
+    /// if they have one, so donor-count goals account for returning donors.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the campaign and the donor's record
+    /// * `amount` - The gross amount the donor would send, in lamports
+    pub fn preview_donation(ctx: Context<PreviewDonation>, amount: u64) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let clock = Clock::get()?;
+
+        campaign.check_accepting_donations(clock.unix_timestamp, clock.slot)?;
+        campaign.check_donation_step(amount)?;
+
+        let fee = campaign.donation_fee(amount)?;
+        let net_to_campaign = amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?;
+        let first_donation = ctx.accounts.donor_record.as_ref().is_none_or(|record| record.donation_count == 0);
+        let mut after = Box::new((**campaign).clone());
+        let matched = after.record_donation(net_to_campaign, first_donation, clock.slot)?;
+        let total_hidden = campaign.total_hidden(clock.unix_timestamp);
+
+        emit!(PreviewResult {
+            campaign: campaign.key(),
+            amount,
+            fee,
+            net_to_campaign,
+            matched,
+            new_total: if total_hidden { 0 } else { after.amount_donated },
+            crosses_goal: !total_hidden && !campaign.goal_reached() && after.goal_reached(),
+        });
+
+        msg!("Donation previewed");
+        Ok(())
+    }
+
+    /// Withdraws up to the campaign's overage: what it raised above its goal
+    ///
+    /// Lets an all-or-nothing campaign spend its surplus before finalization while the goal
+    /// amount stays locked. Everything else works as in `withdraw`.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the campaign, admin and optional destination accounts
+    /// * `amount` - The amount to withdraw in lamports, at most the current overage
+    /// * `withdraw_nonce` - If given, must equal the campaign's `withdraw_nonce`
+    pub fn withdraw_overage(ctx: Context<Withdraw>, amount: u64, withdraw_nonce: Option<u64>) -> Result<()> {
+        withdraw_funds(ctx, amount, withdraw_nonce, true)
+    }
+
+    /// Returns the unused match pool to its sponsor once the pool has expired
+    ///
+    /// Until then the pool keeps matching donations, even past `match_expiry`. Once the
+    /// campaign has failed or been cancelled, the sponsor also gets back the amounts already
+    /// matched, as far as the campaign's free balance covers them; they count as refunded, so
+    /// they no longer hold up `close_campaign`. Callable again to collect the rest.
+    pub fn reclaim_match(ctx: Context<ReclaimMatch>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let clock = Clock::get()?;
+
+        let settling = matches!(campaign.status, CampaignStatus::Cancelled | CampaignStatus::Failed);
+        if !settling && !campaign.match_expired(clock.unix_timestamp, clock.slot) {
+            return Err(ErrorCode::MatchNotExpired.into());
+        }
+        let pool = campaign.match_pool;
+        let matched = if settling {
+            campaign.matched_total.min(campaign.available_lamports(campaign.to_account_info().lamports())?)
+        } else {
+            0
+        };
+        let amount = pool.checked_add(matched).ok_or(ErrorCode::Overflow)?;
+        if amount == 0 {
+            return Err(ErrorCode::MatchPoolEmpty.into());
+        }
+
+        // The pool is held by the program-owned campaign, so lamports can be moved directly
+        move_lamports(&campaign.to_account_info(), &ctx.accounts.sponsor.to_account_info(), amount)?;
+        campaign.match_pool = 0;
+        campaign.matched_total = campaign.matched_total.checked_sub(matched).ok_or(ErrorCode::Overflow)?;
+        campaign.refunded_amount = campaign.refunded_amount.checked_add(matched).ok_or(ErrorCode::Overflow)?;
+        if campaign.matched_total == 0 {
+            campaign.match_sponsor = Pubkey::default();
+        }
+
+        emit!(MatchReclaimed {
+            campaign: campaign.key(),
+            sponsor: ctx.accounts.sponsor.key(),
+            amount,
+            matched_returned: matched,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Match pool reclaimed");
+        Ok(())
+    }
+
+    /// Splits one gift across several campaigns by weight
+    ///
+    /// `remaining_accounts` holds, for each weight in order, the campaign followed by the
+    /// donor's record PDA on it (created here on the wallet's first donation). Each share
+    /// goes through the same checks, fees, matching and donor bookkeeping as `donate`.
+    /// Campaigns needing accounts beyond these (attestations, denylist screening, fee
+    /// recipients paid per donation) must be given to with `donate` instead.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context holding the donor; campaigns and donor records go in `remaining_accounts`
+    /// * `amount` - The whole gift in lamports, before fees
+    /// * `weights` - Each campaign's share in basis points, adding up to 10000, for at least one
+    ///   and at most `MAX_WEIGHTED_CAMPAIGNS` campaigns; the rounding remainder goes to the first
+    pub fn donate_weighted<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DonateWeighted<'info>>,
+        amount: u64,
+        weights: Vec<u16>,
+    ) -> Result<()> {
+        if weights.is_empty() {
+            return Err(ErrorCode::EmptyBatch.into());
+        }
+        if weights.len() > MAX_WEIGHTED_CAMPAIGNS || weights.len() * 2 != ctx.remaining_accounts.len() {
+            return Err(ErrorCode::InvalidWeights.into());
+        }
+        let shares = bps::split_weights(amount, &weights).ok_or(ErrorCode::InvalidWeights)?;
+
+        let user = &ctx.accounts.user;
+        let donor_key = user.key();
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let rent = Rent::get()?;
+        let no_recipient: Option<UncheckedAccount<'info>> = None;
+        for (share, accounts) in shares.into_iter().zip(ctx.remaining_accounts.chunks_exact(2)) {
+            let (campaign_info, record_info) = (&accounts[0], &accounts[1]);
+
+            // Only real campaign PDAs take donations
+            let mut campaign = Account::<Campaign>::try_from(campaign_info)?;
+            let campaign_key = campaign.key();
+            let expected = Pubkey::create_program_address(
+                &[CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key(), &[campaign.bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::BatchMismatch)?;
+            if campaign_key != expected {
+                return Err(ErrorCode::BatchMismatch.into());
+            }
+
+            // Create the donor record on the wallet's first donation, as `donate` would
+            let (record_key, record_bump) =
+                Pubkey::find_program_address(&[DONOR_SEED, campaign_key.as_ref(), donor_key.as_ref()], ctx.program_id);
+            if record_info.key() != record_key {
+                return Err(ErrorCode::BatchMismatch.into());
+            }
+            if record_info.data_is_empty() {
+                let signer_seeds: &[&[u8]] = &[DONOR_SEED, campaign_key.as_ref(), donor_key.as_ref(), &[record_bump]];
+                anchor_lang::system_program::create_account(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::CreateAccount {
+                            from: user.to_account_info(),
+                            to: record_info.clone(),
+                        },
+                        &[signer_seeds],
+                    ),
+                    rent.minimum_balance(DonorRecord::LEN),
+                    DonorRecord::LEN as u64,
+                    ctx.program_id,
+                )?;
+                // The fresh account is zeroed; writing it back stamps the discriminator
+                let record = DonorRecord::try_deserialize_unchecked(&mut &record_info.try_borrow_data()?[..])?;
+                record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+            }
+            let mut donor_record = Account::<DonorRecord>::try_from(record_info)?;
+            let first_donation = donor_record.donation_count == 0;
+
+            campaign.check_accepting_donations(now, clock.slot)?;
+            campaign.check_attestation(None, &donor_key, now)?;
+            campaign.check_denylist(None)?;
+            campaign.check_donor_capacity(first_donation)?;
+            campaign.check_donation_step(share)?;
+            donor_record.check_rate_limit(campaign.min_seconds_between_donations, now)?;
+
+            let net = pay_fees(&mut campaign, &user.to_account_info(), [&no_recipient, &no_recipient], share)?;
+            transfer_lamports(&user.to_account_info(), campaign_info, net)?;
+
+            let donor_hash = campaign.donor_hash(&donor_key);
+            credit_donation(&mut campaign, donor_hash, net, donor_record.hide_amount, first_donation, now, clock.slot)?;
+
+            donor_record.record_donation(campaign_key, donor_key, donor_hash, net, now, record_bump)?;
+            donor_record.weighted_score = donor_record.weighted_score.saturating_add(campaign.time_weighted(net, now));
+
+            emit_donation(&campaign, donor_hash, net, donor_record.hide_amount, now);
+
+            // Accounts loaded from remaining_accounts aren't written back automatically
+            campaign.exit(ctx.program_id)?;
+            donor_record.exit(ctx.program_id)?;
+        }
+
+        msg!("Weighted donation successful");
+        Ok(())
+    }
+
+    /// Puts the signer in line for a spot on a campaign that has reached its donor cap
+    ///
+    /// Spots free up when donors leave (e.g. through refunds); `promote_from_waitlist` then
+    /// holds them for waitlisted wallets in the order they joined.
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let clock = Clock::get()?;
+
+        campaign.check_accepting_donations(clock.unix_timestamp, clock.slot)?;
+        if campaign.has_free_spot() {
+            return Err(ErrorCode::CampaignNotFull.into());
+        }
+
+        let entry = &mut ctx.accounts.entry;
+        entry.campaign = campaign.key();
+        entry.donor = ctx.accounts.user.key();
+        entry.position = campaign.waitlist_next;
+        entry.joined_at = clock.unix_timestamp;
+        entry.promoted = false;
+        entry.bump = ctx.bumps.entry;
+        campaign.waitlist_next = campaign.waitlist_next.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        msg!("Joined waitlist");
+        Ok(())
+    }
+
+    /// Holds a freed donor spot for the wallet at the head of the waitlist
+    ///
+    /// Permissionless, so keepers can advance the line. The promoted wallet claims the spot
+    /// by donating with its waitlist entry.
+    pub fn promote_from_waitlist(ctx: Context<PromoteFromWaitlist>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let entry = &mut ctx.accounts.entry;
+
+        if entry.promoted || entry.position != campaign.waitlist_head {
+            return Err(ErrorCode::NotNextInWaitlist.into());
+        }
+        if !campaign.has_free_spot() {
+            return Err(ErrorCode::NoFreeSpot.into());
+        }
+
+        entry.promoted = true;
+        campaign.promoted_spots = campaign.promoted_spots.saturating_add(1);
+        campaign.waitlist_head = campaign.waitlist_head.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        emit!(WaitlistPromoted {
+            campaign: campaign.key(),
+            donor: entry.donor,
+            position: entry.position,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Waitlist entry promoted");
+        Ok(())
+    }
+
+}
+
+/// Moves a flagged donor's `amount` into the campaign without crediting it, holding it in
+/// their `QuarantinedDonation` until the config authority releases it
+///
+/// `bumps` are the donor record's and the quarantine's. No fee is taken: a rejected donation
+/// is refunded in full.
+fn quarantine_donation<'info>(
+    campaign: &mut Account<'info, Campaign>,
+    donor_record: &mut Account<'info, DonorRecord>,
+    quarantine: &mut Account<'info, QuarantinedDonation>,
+    user: &AccountInfo<'info>,
+    amount: u64,
+    now: i64,
+    bumps: (u8, u8),
+) -> Result<()> {
+    transfer_lamports(user, &campaign.to_account_info(), amount)?;
+
+    let campaign_key = campaign.key();
+    let donor_key = user.key();
+    quarantine.campaign = campaign_key;
+    quarantine.donor = donor_key;
+    quarantine.amount = quarantine.amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    quarantine.quarantined_at = now;
+    quarantine.bump = bumps.1;
+
+    // Keep the record addressable so the donation can be credited on release
+    donor_record.campaign = campaign_key;
+    donor_record.donor = donor_key;
+    donor_record.donor_hash = campaign.donor_hash(&donor_key);
+    donor_record.bump = bumps.0;
+
+    campaign.quarantined = campaign.quarantined.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+    emit!(DonationQuarantined {
+        campaign: campaign_key,
+        donor: donor_key,
+        amount,
+        total_quarantined: quarantine.amount,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Emits `CampaignCreated` for a freshly initialized campaign
+fn emit_created(key: Pubkey, campaign: &Campaign) -> Result<()> {
+    emit!(CampaignCreated {
+        campaign: key,
+        admin: campaign.admin,
+        goal: campaign.goal,
+        deadline: campaign.deadline,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Transfers lamports out of a system-owned account (the signer) via the system program
+fn transfer_lamports<'info>(from: &AccountInfo<'info>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let ix = anchor_lang::solana_program::system_instruction::transfer(from.key, to.key, amount);
+    anchor_lang::solana_program::program::invoke(&ix, &[from.clone(), to.clone()])?;
+    Ok(())
+}
+
+/// Checks that `account` gained at least `credited` lamports since it held `balance_before`
+///
+/// Guards against a refactor crediting a donation to the totals without moving the funds.
+#[cfg(feature = "strict-invariants")]
+fn check_inflow(account: &AccountInfo, balance_before: u64, credited: u64) -> Result<()> {
+    if account.lamports().saturating_sub(balance_before) < credited {
+        return Err(ErrorCode::InflowMismatch.into());
+    }
+    Ok(())
+}
+
+/// Moves lamports directly between accounts; `from` must be owned by this program
+///
+/// Fails with `InsufficientFunds` instead of underflowing `from`'s balance.
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let remaining = from.lamports().checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+    let credited = to.lamports().checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? = credited;
+    Ok(())
+}
+
+/// Moves `amount` out of the campaign for `withdraw` and `withdraw_overage`
+///
+/// With `overage_only`, only the surplus above the goal may leave, even while the
+/// rest of an all-or-nothing campaign's funds are reserved.
+fn withdraw_funds(ctx: Context<Withdraw>, amount: u64, withdraw_nonce: Option<u64>, overage_only: bool) -> Result<()> {
+    // The context already checks that the campaign is a campaign PDA and the signer its admin
+    let campaign = &mut ctx.accounts.campaign;
+    let admin = &ctx.accounts.admin;
+
+    if withdraw_nonce.is_some_and(|nonce| nonce != campaign.withdraw_nonce) {
+        return Err(ErrorCode::WithdrawReplay.into());
+    }
+
+    // Funds that may still be owed to donors are reserved for refunds
+    let now = Clock::get()?.unix_timestamp;
+    if overage_only {
+        campaign.check_overage_withdrawable(now, amount)?;
+    } else {
+        campaign.check_withdrawable(now, Clock::get()?.slot)?;
+    }
+    campaign.check_withdraw_condition(ctx.accounts.withdraw_condition.as_ref().map(|c| c.as_ref()))?;
+
+    // A disputed campaign's withdrawals are held in escrow until the dispute is resolved
+    let recipient = if campaign.disputed {
+        let escrow = ctx.accounts.dispute_escrow.as_mut().ok_or(ErrorCode::DisputeEscrowRequired)?;
+        escrow.campaign = campaign.key();
+        escrow.amount = escrow.amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        escrow.bump = ctx.bumps.dispute_escrow.unwrap_or_default();
+        escrow.to_account_info()
+    } else {
+        let recipient = match &ctx.accounts.destination {
+            Some(destination) => destination.to_account_info(),
+            None => admin.to_account_info(),
+        };
+        campaign.check_destination(recipient.key)?;
+        recipient
+    };
+
+    // Current lamports in the campaign account
+    let campaign_lamports = **campaign.to_account_info().lamports.borrow();
+
+    // Check if enough lamports are available to withdraw above the rent-exempt minimum,
+    // the unspent match pool (which belongs to sponsors), unclaimed fees, pending donations
+    // and the endowment
+    let spendable = campaign.spendable_lamports(campaign_lamports, Clock::get()?.slot)?;
+    if spendable < amount {
+        if spendable.saturating_add(campaign.endowment_total) >= amount {
+            return Err(ErrorCode::EndowmentProtected.into());
+        }
+        return Err(ErrorCode::InsufficientFunds.into());
+    }
+
+    // The platform takes its fee from payouts; escrowed funds, fee-exempt campaigns and campaigns
+    // with an allowlist pay none, as does every campaign before the config is initialized
+    let config = ProgramConfig::load(&ctx.accounts.config)?;
+    let platform_fee = match &config {
+        Some(config) if !campaign.disputed && campaign.owes_platform_fee() => {
+            bps::apply(amount, config.fee_bps).ok_or(ErrorCode::InvalidFeeBps)?
+        }
+        _ => 0,
+    };
+    if platform_fee > 0 {
+        let treasury = ctx.accounts.treasury.as_ref().ok_or(ErrorCode::TreasuryRequired)?;
+        if config.is_none_or(|config| treasury.key() != config.treasury) {
+            return Err(ErrorCode::InvalidTreasury.into());
+        }
+        move_lamports(&campaign.to_account_info(), &treasury.to_account_info(), platform_fee)?;
+    }
+
+    // Transfer the rest from campaign to the recipient
+    let net_amount = amount.checked_sub(platform_fee).ok_or(ErrorCode::Overflow)?;
+    move_lamports(&campaign.to_account_info(), &recipient, net_amount)?;
+    campaign.amount_withdrawn = campaign.amount_withdrawn.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    campaign.record_period_withdrawal(amount, now)?;
+    campaign.withdraw_nonce = campaign.withdraw_nonce.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+    emit!(FundsWithdrawn {
+        campaign: campaign.key(),
+        recipient: recipient.key(),
+        amount,
+        platform_fee,
+        total_withdrawn: campaign.amount_withdrawn,
+        escrowed: campaign.disputed,
+        timestamp: now,
+    });
+
+    audit(&ctx.accounts.audit_log, campaign.key(), AuditAction::Withdraw, admin.key(), amount)?;
+
+    if campaign.recompute_status(now, Clock::get()?.slot) {
+        emit_finalized(campaign, now);
+    }
+
+    msg!("Withdrawal successful");
+    Ok(())
+}
+
+/// Pays the campaign's fee on `amount` from `payer` to its fee recipients and returns the net amount
+///
+/// `fee_accounts` are the recipients' accounts, in the campaign's order; each is only
+/// required when its share is non-zero. If the campaign accrues fees, the whole fee is moved
+/// into the campaign instead and credited to `fees_accrued`, so no recipient account is needed.
+/// A campaign the config authority made fee-exempt pays no fee at all, whatever its `fee_bps`.
+fn pay_fees<'info>(
+    campaign: &mut Account<'info, Campaign>,
+    payer: &AccountInfo<'info>,
+    fee_accounts: [&Option<UncheckedAccount<'info>>; 2],
+    amount: u64,
+) -> Result<u64> {
+    let shares = campaign.fee_shares(amount)?;
+    let fee: u64 = shares.iter().sum();
+    if fee == 0 {
+        return Ok(amount);
+    }
+    if campaign.accrue_fees {
+        transfer_lamports(payer, &campaign.to_account_info(), fee)?;
+        for (accrued, share) in campaign.fees_accrued.iter_mut().zip(shares) {
+            *accrued = accrued.checked_add(share).ok_or(ErrorCode::Overflow)?;
+        }
+        return Ok(amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?);
+    }
+    for ((share, recipient), account) in shares.iter().zip(campaign.fee_recipients).zip(fee_accounts) {
+        if *share == 0 {
+            continue;
+        }
+        let account = account.as_ref().ok_or(ErrorCode::FeeRecipientMismatch)?;
+        if account.key() != recipient.recipient {
+            return Err(ErrorCode::FeeRecipientMismatch.into());
+        }
+        transfer_lamports(payer, &account.to_account_info(), *share)?;
+    }
+    Ok(amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?)
+}
+
+/// Closes `account` to `destination` if it is the PDA derived from `seeds` (bump included)
+fn close_aux_account<'info, T>(account: &Account<'info, T>, seeds: &[&[u8]], destination: &AccountInfo<'info>) -> Result<()>
+where
+    T: AccountSerialize + AccountDeserialize + Owner + Clone,
+{
+    let expected = Pubkey::create_program_address(seeds, &crate::ID).map_err(|_| ErrorCode::AuxAccountMismatch)?;
+    if account.key() != expected {
+        return Err(ErrorCode::AuxAccountMismatch.into());
+    }
+    account.close(destination.clone())
+}
+
+/// Emits the donation event the campaign's `event_level` asks for
+///
+/// Public feeds get the pseudonymous donor hash rather than the raw pubkey.
+fn emit_donation(campaign: &Account<Campaign>, donor_hash: [u8; 32], amount: u64, amount_hidden: bool, now: i64) {
+    let amount = if amount_hidden { 0 } else { amount };
+    match campaign.event_level {
+        EventLevel::None => {}
+        EventLevel::Minimal => emit!(DonationLogged {
+            campaign: campaign.key(),
+            amount,
+            timestamp: now,
+        }),
+        EventLevel::Full => {
+            let total_hidden = campaign.total_hidden(now);
+            emit!(DonationReceived {
+                campaign: campaign.key(),
+                donor_hash,
+                amount,
+                amount_hidden,
+                new_total: if total_hidden { 0 } else { campaign.amount_donated },
+                verified_raised: if total_hidden { 0 } else { campaign.verified_raised },
+                total_hidden,
+                currency_symbol: campaign.currency_symbol().to_string(),
+                thank_you: campaign.thank_you().to_string(),
+                timestamp: now,
+            });
+        }
+    }
+}
+
+/// Credits a donation's net `amount` to the campaign through `Campaign::record_donation`, then
+/// extends the deadline for a late donation
+///
+/// Emits `HardGoalReached`, `DonationMatched` and `DeadlineExtended` as they happen; the
+/// caller records the donor and emits the donation event. With `amount_hidden`, the match is
+/// published without its figures, since the matched amount (or the drop in the pool) is the
+/// donation amount until the pool runs low.
+fn credit_donation(
+    campaign: &mut Account<Campaign>,
+    donor_hash: [u8; 32],
+    amount: u64,
+    amount_hidden: bool,
+    first_donation: bool,
+    now: i64,
+    slot: u64,
+) -> Result<()> {
+    let hard_goal_was_reached = campaign.hard_goal_reached();
+    let matched = campaign.record_donation(amount, first_donation, slot)?;
+    if !hard_goal_was_reached {
+        emit_hard_goal(campaign, now);
+    }
+    if matched > 0 {
+        emit!(DonationMatched {
+            campaign: campaign.key(),
+            donor_hash,
+            amount: if amount_hidden { 0 } else { matched },
+            amount_hidden,
+            remaining_pool: if amount_hidden { 0 } else { campaign.match_pool },
+            timestamp: now,
+        });
+    }
+
+    // A donation landing just before the deadline gives others time to respond
+    if let Some(previous_deadline) = campaign.extend_for_late_donation(now) {
+        emit!(DeadlineExtended {
+            campaign: campaign.key(),
+            previous_deadline,
+            deadline: campaign.deadline,
+            timestamp: now,
+        });
+    }
+    Ok(())
+}
+
+/// Emits `HardGoalReached` if the campaign now meets its stretch goal
+///
+/// Callers only invoke it when the goal wasn't met before the donation. Skipped while the
+/// campaign hides its total, since the event would give the total away.
+fn emit_hard_goal(campaign: &Account<Campaign>, now: i64) {
+    if campaign.hard_goal_reached() && !campaign.total_hidden(now) {
+        emit!(HardGoalReached {
+            campaign: campaign.key(),
+            hard_goal: campaign.hard_goal,
+            progress: campaign.goal_progress(),
+            timestamp: now,
+        });
+    }
+}
+
+/// Emits `CampaignFinalized` for a campaign that has just ended
+fn emit_finalized(campaign: &Account<Campaign>, now: i64) {
+    emit!(CampaignFinalized {
+        campaign: campaign.key(),
+        status: campaign.status,
+        net_raised: campaign.net_raised(),
+        currency_symbol: campaign.currency_symbol().to_string(),
+        timestamp: now,
+    });
+}
+
+/// Appends a privileged action to a campaign's audit log, initializing the log on its first entry
+fn audit(audit_log: &AccountLoader<AuditLog>, campaign: Pubkey, action: AuditAction, actor: Pubkey, amount: u64) -> Result<()> {
+    // A log created by this instruction still has a zeroed discriminator
+    let is_new = audit_log.as_ref().try_borrow_data()?[..DISCRIMINATOR_LEN].iter().all(|b| *b == 0);
+    let mut log = if is_new { audit_log.load_init()? } else { audit_log.load_mut()? };
+    if is_new {
+        log.campaign = campaign;
+    }
+    log.append(action, actor, amount, Clock::get()?.unix_timestamp);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: CampaignParams)]
+pub struct Create<'info> {
+    /// Initializes the campaign account with PDA (Program Derived Address)
+    /// Uses seeds = [b"campaign", user key, idempotency key] to derive unique address;
+    /// without a key (all zero) it is the original `[b"campaign", user key]`.
+    /// `init_if_needed` lets a retried create reach the handler, which rejects it with `AlreadyCreated`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Campaign::LEN + usize::from(params.extra_space), // Campaign struct plus requested headroom
+        seeds = [CAMPAIGN_SEED, user.key().as_ref(), croudfunding_types::campaign_seed_key(&params.idempotency_key)],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>, // Mutable new campaign account
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The user creating the campaign (payer and signer)
+
+    pub system_program: Program<'info, System>, // Required for account creation and rent
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// Must be a campaign PDA run by the signing admin
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key()],
+        bump = campaign.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>, // Mutable campaign account for withdrawal
+
+    #[account(mut)]
+    pub admin: Signer<'info>, // The campaign admin
+
+    /// CHECK: The config PDA holding the platform fee settings; it's empty (and no fee is taken)
+    /// until `initialize_config` has run, and deserialized in the handler otherwise
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the config's treasury, checked in the handler; only required when the
+    /// withdrawal owes the platform fee
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Any account may receive the withdrawal unless the campaign has an allowlist, checked in the handler
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>, // Recipient of the funds (defaults to the admin)
+
+    /// Escrow receiving the funds instead while the campaign is disputed
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = DisputeEscrow::LEN,
+        seeds = [DISPUTE_ESCROW_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub dispute_escrow: Option<Account<'info, DisputeEscrow>>,
+
+    /// CHECK: Must be the campaign's withdraw_condition, owned by its trusted program; checked in the handler
+    pub withdraw_condition: Option<UncheckedAccount<'info>>,
+
+    /// Audit log of the campaign, created on its first privileged action at the actor's expense
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, org: Option<Pubkey>)]
+pub struct Donate<'info> {
+    /// Must be a campaign PDA, so donations can't be logged against an arbitrary account
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    /// Per-donor record, created on the wallet's first donation to this campaign.
+    /// Anchor creates it before the handler moves any SOL, so a donor who can't cover
+    /// its rent fails the whole instruction instead of donating unrecorded.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = DonorRecord::LEN,
+        seeds = [DONOR_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub donor_record: Account<'info, DonorRecord>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Must match the campaign's first fee recipient; only required when it receives a share
+    #[account(mut)]
+    pub fee_recipient_a: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must match the campaign's second fee recipient; only required when it receives a share
+    #[account(mut)]
+    pub fee_recipient_b: Option<UncheckedAccount<'info>>,
+    /// Running total of the organization the donation is attributed to; required exactly when `org` is given
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = OrgDonation::LEN,
+        seeds = [ORG_SEED, campaign.key().as_ref(), org.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub org_donation: Option<Account<'info, OrgDonation>>,
+    /// Donor's attestation; only required when the campaign requires one
+    pub attestation: Option<Account<'info, Attestation>>,
+    /// CHECK: Address of the donor's entry on the campaign's denylist, which may not exist;
+    /// required when the campaign screens donors
+    #[account(seeds = [DENYLIST_SEED, campaign.denylist.as_ref(), user.key().as_ref()], bump)]
+    pub denylist_entry: Option<UncheckedAccount<'info>>,
+    /// Holds the donation if the donor is flagged; only required for flagged donors
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = QuarantinedDonation::LEN,
+        seeds = [QUARANTINE_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub quarantine: Option<Account<'info, QuarantinedDonation>>,
+    /// Donor's promoted waitlist entry, closed in the handler as they take their spot; only needed at the donor cap
+    #[account(
+        mut,
+        seeds = [WAITLIST_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump = waitlist_entry.bump
+    )]
+    pub waitlist_entry: Option<Account<'info, WaitlistEntry>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DonateFromCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, source.creator.as_ref(), source.seed_key()],
+        bump = source.bump
+    )]
+    pub source: Account<'info, Campaign>, // Campaign the funds are taken from
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, destination.creator.as_ref(), destination.seed_key()],
+        bump = destination.bump
+    )]
+    pub destination: Account<'info, Campaign>, // Campaign receiving the donation
+
+    /// Donor record of the source campaign on the destination campaign
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = DonorRecord::LEN,
+        seeds = [DONOR_SEED, destination.key().as_ref(), source.key().as_ref()],
+        bump
+    )]
+    pub donor_record: Account<'info, DonorRecord>,
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The source campaign's admin
+
+    /// Audit log of the campaign, created on its first privileged action at the actor's expense
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, source.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FollowCampaign<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign being followed
+
+    #[account(
+        init,
+        payer = user,
+        space = Follow::LEN,
+        seeds = [FOLLOW_SEED, user.key().as_ref(), campaign.key().as_ref()],
+        bump
+    )]
+    pub follow: Account<'info, Follow>, // One follow PDA per (user, campaign)
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The follower (payer and signer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnfollowCampaign<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign being unfollowed
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [FOLLOW_SEED, user.key().as_ref(), campaign.key().as_ref()],
+        bump = follow.bump
+    )]
+    pub follow: Account<'info, Follow>, // Closed, rent goes back to the user
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The follower
+}
+
+#[derive(Accounts)]
+pub struct MigrateCampaign<'info> {
+    /// CHECK: Still in the legacy layout, so it can't be loaded as `Campaign`;
+    /// the handler checks its size, discriminator and admin
+    #[account(mut, owner = crate::ID, seeds = [CAMPAIGN_SEED, user.key().as_ref()], bump)]
+    pub campaign: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The campaign's admin, paying the rent for the larger layout
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign the refund is paid from
+
+    #[account(
+        mut,
+        seeds = [DONOR_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump = donor_record.bump
+    )]
+    pub donor_record: Account<'info, DonorRecord>, // Closed once the donor is fully refunded
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The donor being refunded
+}
+
+#[derive(Accounts)]
+pub struct UpdateCampaign<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign being updated by its admin
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The campaign's admin (its pending admin for `accept_admin`)
+
+    /// Audit log of the campaign, created on its first privileged action at the actor's expense
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Finalize<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign being finalized; anyone may call
+}
+
+#[derive(Accounts)]
+pub struct FundMatchPool<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign whose donations get matched
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>, // Wallet funding the pool; anyone may sponsor an empty pool
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ArchiveSnapshot<'info> {
+    pub campaign: Account<'info, Campaign>, // Campaign being archived
+
+    #[account(
+        init,
+        payer = user,
+        space = CampaignArchive::LEN,
+        seeds = [ARCHIVE_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub archive: Account<'info, CampaignArchive>, // One immutable archive per campaign
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The campaign's admin (payer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAux<'info> {
+    /// CHECK: Address of the closed campaign; the handler checks it is no longer a program account
+    pub campaign: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ARCHIVE_SEED, campaign.key().as_ref()],
+        bump,
+        constraint = archive.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub archive: Account<'info, CampaignArchive>, // Outlives the campaign and records its admin
+
+    pub admin: Signer<'info>, // The campaign's admin at archival
+
+    /// CHECK: Receives the reclaimed rent; must be the target recorded in the archive
+    #[account(mut, address = archive.rent_sweep_target @ ErrorCode::InvalidRentSweepTarget)]
+    pub rent_sweep_target: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCampaign<'info> {
+    #[account(mut, close = user)]
+    pub campaign: Account<'info, Campaign>, // Closed, remaining lamports go to the admin
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = CampaignArchive::LEN,
+        seeds = [ARCHIVE_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub archive: Account<'info, CampaignArchive>, // Created here unless the campaign was archived before closing
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The campaign's admin
+
+    /// Audit log of the campaign, created on its first privileged action at the actor's expense
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferToCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, source.creator.as_ref(), source.seed_key()],
+        bump = source.bump
+    )]
+    pub source: Account<'info, Campaign>, // Campaign whose balance is moved
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, destination.creator.as_ref(), destination.seed_key()],
+        bump = destination.bump
+    )]
+    pub destination: Account<'info, Campaign>, // Campaign receiving the balance
+
+    /// Donor record of the source campaign on the destination campaign
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = DonorRecord::LEN,
+        seeds = [DONOR_SEED, destination.key().as_ref(), source.key().as_ref()],
+        bump
+    )]
+    pub donor_record: Account<'info, DonorRecord>,
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The source campaign's admin
+
+    /// Audit log of the source campaign, created on its first privileged action at the actor's expense
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, source.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MakePledge<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>, // Campaign being pledged to
+
+    #[account(
+        init,
+        payer = user,
+        space = Pledge::LEN,
+        seeds = [PLEDGE_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub pledge: Account<'info, Pledge>, // One open pledge per (campaign, donor)
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The pledging donor (payer and signer)
+
+    /// CHECK: Address of the donor's entry on the campaign's denylist, which may not exist;
+    /// required when the campaign screens donors
+    #[account(seeds = [DENYLIST_SEED, campaign.denylist.as_ref(), user.key().as_ref()], bump)]
+    pub denylist_entry: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillPledge<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>, // Campaign receiving the donation
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [PLEDGE_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump = pledge.bump
+    )]
+    pub pledge: Account<'info, Pledge>, // Closed once funded, rent goes back to the donor
+
+    /// Per-donor record, created on the wallet's first donation to this campaign
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = DonorRecord::LEN,
+        seeds = [DONOR_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub donor_record: Account<'info, DonorRecord>,
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The pledging donor
+
+    /// CHECK: Must match the campaign's first fee recipient; only required when it receives a share
+    #[account(mut)]
+    pub fee_recipient_a: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must match the campaign's second fee recipient; only required when it receives a share
+    #[account(mut)]
+    pub fee_recipient_b: Option<UncheckedAccount<'info>>,
+
+    /// Donor's attestation; only required when the campaign requires one
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    /// CHECK: Address of the donor's entry on the campaign's denylist, which may not exist;
+    /// required when the campaign screens donors
+    #[account(seeds = [DENYLIST_SEED, campaign.denylist.as_ref(), user.key().as_ref()], bump)]
+    pub denylist_entry: Option<UncheckedAccount<'info>>,
+    /// Holds the pledged amount if the donor has been flagged; only required for flagged donors
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = QuarantinedDonation::LEN,
+        seeds = [QUARANTINE_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub quarantine: Option<Account<'info, QuarantinedDonation>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpirePledge<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign the pledge was made to
+
+    #[account(
+        mut,
+        close = donor,
+        has_one = campaign,
+        has_one = donor,
+        seeds = [PLEDGE_SEED, campaign.key().as_ref(), donor.key().as_ref()],
+        bump = pledge.bump
+    )]
+    pub pledge: Account<'info, Pledge>, // Closed, rent goes back to the donor
+
+    /// CHECK: Only receives the pledge's rent; must be the pledge's donor
+    #[account(mut)]
+    pub donor: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAmountVisibility<'info> {
+    pub campaign: Account<'info, Campaign>, // Campaign the donor gives to
+
+    /// Per-donor record, created here if the donor hasn't donated yet
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = DonorRecord::LEN,
+        seeds = [DONOR_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub donor_record: Account<'info, DonorRecord>,
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The donor
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankRefund<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>, // Failed campaign the refund is paid from
+
+    #[account(
+        mut,
+        close = donor,
+        has_one = donor,
+        seeds = [DONOR_SEED, campaign.key().as_ref(), donor.key().as_ref()],
+        bump = donor_record.bump
+    )]
+    pub donor_record: Account<'info, DonorRecord>, // Closed, rent goes back to the donor
+
+    /// CHECK: Receives the refund; must be the donor record's donor
+    #[account(mut)]
+    pub donor: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>, // Anyone; receives the crank bounty
+}
+
+#[derive(Accounts)]
+#[instruction(subject: Pubkey)]
+pub struct IssueAttestation<'info> {
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = Attestation::LEN,
+        seeds = [ATTESTATION_SEED, issuer.key().as_ref(), subject.as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>, // One attestation per (issuer, subject)
+
+    #[account(mut)]
+    pub issuer: Signer<'info>, // The attesting issuer (payer and signer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        mut,
+        close = issuer,
+        has_one = issuer,
+        seeds = [ATTESTATION_SEED, issuer.key().as_ref(), attestation.subject.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>, // Closed, rent goes back to the issuer
+
+    #[account(mut)]
+    pub issuer: Signer<'info>, // The issuer that created the attestation
+}
+
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    pub campaign: Account<'info, Campaign>, // Campaign being reconciled; anyone may call
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::LEN,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>, // Program-wide singleton
+
+    #[account(mut)]
+    pub authority: Signer<'info>, // The program's upgrade authority, becomes the config authority
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Croudfunding>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>, // The config authority
+
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign put under dispute
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>, // The config authority
+
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Disputed campaign
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = campaign,
+        seeds = [DISPUTE_ESCROW_SEED, campaign.key().as_ref()],
+        bump = dispute_escrow.bump
+    )]
+    pub dispute_escrow: Account<'info, DisputeEscrow>, // Escrow holding the withdrawn funds, closed to the admin
+
+    /// CHECK: Receives the funds when released to the admin; must be the campaign's admin
+    #[account(mut, address = campaign.admin @ ErrorCode::Unauthorized)]
+    pub admin: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigInfo<'info> {
+    pub campaign: Account<'info, Campaign>, // Campaign whose settings are emitted; anyone may call
+}
+
+#[derive(Accounts)]
+pub struct AcceptRival<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Challenged campaign, accepting through its admin
+
+    #[account(mut)]
+    pub challenger: Account<'info, Campaign>, // Campaign that proposed the rivalry
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The challenged campaign's admin
+
+    /// Audit log of the challenged campaign, created on its first privileged action at the actor's expense
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CompareRivals<'info> {
+    pub campaign: Account<'info, Campaign>, // Campaign whose standings are emitted; anyone may call
+    pub rival: Account<'info, Campaign>,    // The campaign's linked rival
+}
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign holding the accrued fees
+
+    #[account(mut)]
+    pub recipient: Signer<'info>, // One of the campaign's fee recipients
+}
+
+#[derive(Accounts)]
+pub struct PostMessage<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign the message is posted on
+
+    #[account(seeds = [DONOR_SEED, campaign.key().as_ref(), author.key().as_ref()], bump = donor_record.bump)]
+    pub donor_record: Account<'info, DonorRecord>, // Author's donor record; only donors may post
+
+    #[account(
+        init,
+        payer = author,
+        space = DonationMessage::LEN,
+        seeds = [MESSAGE_SEED, campaign.key().as_ref(), campaign.message_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub message: Account<'info, DonationMessage>, // Next message in the campaign's feed
+
+    #[account(mut)]
+    pub author: Signer<'info>, // The donor posting (payer and signer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeriveCampaign {}
+
+#[derive(Accounts)]
+#[instruction(subject: Pubkey)]
+pub struct FlagAddress<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DenylistEntry::LEN,
+        seeds = [DENYLIST_SEED, authority.key().as_ref(), subject.as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, DenylistEntry>, // One entry per (authority, subject)
+
+    #[account(mut)]
+    pub authority: Signer<'info>, // The denylist authority (payer and signer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnflagAddress<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        seeds = [DENYLIST_SEED, authority.key().as_ref(), entry.subject.as_ref()],
+        bump = entry.bump
+    )]
+    pub entry: Account<'info, DenylistEntry>, // Closed, rent goes back to the authority
+
+    #[account(mut)]
+    pub authority: Signer<'info>, // The authority that flagged the wallet
+}
+
+#[derive(Accounts)]
+pub struct ReleaseQuarantine<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>, // The config authority
+
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign holding the quarantined funds
+
+    #[account(
+        mut,
+        close = donor,
+        has_one = campaign,
+        has_one = donor,
+        seeds = [QUARANTINE_SEED, campaign.key().as_ref(), donor.key().as_ref()],
+        bump = quarantine.bump
+    )]
+    pub quarantine: Account<'info, QuarantinedDonation>, // Closed, rent goes back to the donor
+
+    #[account(mut, seeds = [DONOR_SEED, campaign.key().as_ref(), donor.key().as_ref()], bump = donor_record.bump)]
+    pub donor_record: Account<'info, DonorRecord>, // Donor's record, credited if the funds are accepted
+
+    /// CHECK: The flagged donor; receives refunded funds and the quarantine's rent
+    #[account(mut)]
+    pub donor: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MergeDonations<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>, // Campaign both records belong to
+
+    #[account(
+        mut,
+        close = old_donor,
+        seeds = [DONOR_SEED, campaign.key().as_ref(), old_donor.key().as_ref()],
+        bump = old_record.bump
+    )]
+    pub old_record: Account<'info, DonorRecord>, // Closed, rent goes back to the old wallet
+
+    /// New wallet's record, created here if it hasn't donated yet
+    #[account(
+        init_if_needed,
+        payer = new_donor,
+        space = DonorRecord::LEN,
+        seeds = [DONOR_SEED, campaign.key().as_ref(), new_donor.key().as_ref()],
+        bump
+    )]
+    pub new_record: Account<'info, DonorRecord>,
+
+    #[account(mut, constraint = old_donor.key() != new_donor.key() @ ErrorCode::MergeIntoSelf)]
+    pub old_donor: Signer<'info>, // The wallet being retired
+
+    #[account(mut)]
+    pub new_donor: Signer<'info>, // The wallet taking over the history (payer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCampaignFeeExempt<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>, // The config authority
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed_key()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>, // Campaign whose fee is waived or reinstated
+
+    /// Audit log of the campaign, created on its first privileged action at the actor's expense
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, campaign.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewDonation<'info> {
+    pub campaign: Account<'info, Campaign>, // Campaign the donation would go to; anyone may call
+
+    #[account(has_one = campaign)]
+    pub donor_record: Option<Account<'info, DonorRecord>>, // Donor's record, if they have donated before
+}
+
+#[derive(Accounts)]
+pub struct ReclaimMatch<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign holding the unused pool
+
+    #[account(mut, address = campaign.match_sponsor @ ErrorCode::MatchSponsorMismatch)]
+    pub sponsor: Signer<'info>, // The wallet that funded the pool
+}
+
+#[derive(Accounts)]
+pub struct DonateWeighted<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>, // The donor (payer and signer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign at its donor cap
+
+    #[account(
+        init,
+        payer = user,
+        space = WaitlistEntry::LEN,
+        seeds = [WAITLIST_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, WaitlistEntry>, // One entry per (campaign, wallet)
+
+    #[account(mut)]
+    pub user: Signer<'info>, // The waiting wallet (payer and signer)
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PromoteFromWaitlist<'info> {
+    #[account(mut)]
+    pub campaign: Account<'info, Campaign>, // Campaign a spot freed up on; anyone may call
+
+    #[account(
+        mut,
+        has_one = campaign,
+        seeds = [WAITLIST_SEED, campaign.key().as_ref(), entry.donor.as_ref()],
+        bump = entry.bump
+    )]
+    pub entry: Account<'info, WaitlistEntry>, // Entry at the head of the line
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformFee<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>, // The config authority
+}
+
+#[derive(Accounts)]
+pub struct CreateMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>, // The user creating the campaigns (payer, signer and admin)
+
+    pub system_program: Program<'info, System>, // Required for account creation and rent
+}
+
+// Account, event and error definitions are shared with the `croudfunding-types` crate,
+// which expands the same macros with plain borsh derives for off-chain consumers
+croudfunding_types::campaign_types!(#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]);
+croudfunding_types::campaign_accounts!(#[account]);
+croudfunding_types::audit_log_accounts!(entry: #[zero_copy], log: #[account(zero_copy)]);
+croudfunding_types::campaign_events!(#[event]);
+
+macro_rules! define_error_code {
+    ($($variants:tt)*) => {
+        #[error_code]
+        pub enum ErrorCode {
+            $($variants)*
+        }
+    };
+}
+croudfunding_types::campaign_errors!(define_error_code);
+
+/// Returns true if an Anchor discriminator equals the constant published by the types crate
+const fn discriminator_matches(actual: &[u8], expected: &[u8; 8]) -> bool {
+    if actual.len() != expected.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < expected.len() {
+        if actual[i] != expected[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// Fail the build if a published discriminator drifts from what Anchor derives
+const _: () = assert!(discriminator_matches(Campaign::DISCRIMINATOR, &CAMPAIGN_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonorRecord::DISCRIMINATOR, &DONOR_RECORD_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignDonationSent::DISCRIMINATOR, &CAMPAIGN_DONATION_SENT_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignDonationReceived::DISCRIMINATOR, &CAMPAIGN_DONATION_RECEIVED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(Follow::DISCRIMINATOR, &FOLLOW_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignCancelled::DISCRIMINATOR, &CAMPAIGN_CANCELLED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignReopened::DISCRIMINATOR, &CAMPAIGN_REOPENED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonationReceived::DISCRIMINATOR, &DONATION_RECEIVED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(AuditLog::DISCRIMINATOR, &AUDIT_LOG_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignFinalized::DISCRIMINATOR, &CAMPAIGN_FINALIZED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(OrgDonation::DISCRIMINATOR, &ORG_DONATION_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonationMatched::DISCRIMINATOR, &DONATION_MATCHED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(MatchPoolFunded::DISCRIMINATOR, &MATCH_POOL_FUNDED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignArchive::DISCRIMINATOR, &CAMPAIGN_ARCHIVE_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(FundsMerged::DISCRIMINATOR, &FUNDS_MERGED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(Pledge::DISCRIMINATOR, &PLEDGE_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(Attestation::DISCRIMINATOR, &ATTESTATION_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(Reconciliation::DISCRIMINATOR, &RECONCILIATION_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(ProgramConfig::DISCRIMINATOR, &PROGRAM_CONFIG_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DisputeEscrow::DISCRIMINATOR, &DISPUTE_ESCROW_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DisputeRaised::DISCRIMINATOR, &DISPUTE_RAISED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DisputeResolved::DISCRIMINATOR, &DISPUTE_RESOLVED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignConfig::DISCRIMINATOR, &CAMPAIGN_CONFIG_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(RivalryLinked::DISCRIMINATOR, &RIVALRY_LINKED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(RivalryStandings::DISCRIMINATOR, &RIVALRY_STANDINGS_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(FeesClaimed::DISCRIMINATOR, &FEES_CLAIMED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonationMessage::DISCRIMINATOR, &DONATION_MESSAGE_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(ListingChanged::DISCRIMINATOR, &LISTING_CHANGED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonationLogged::DISCRIMINATOR, &DONATION_LOGGED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DeadlineExtended::DISCRIMINATOR, &DEADLINE_EXTENDED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignDerived::DISCRIMINATOR, &CAMPAIGN_DERIVED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(HardGoalReached::DISCRIMINATOR, &HARD_GOAL_REACHED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonationsSynced::DISCRIMINATOR, &DONATIONS_SYNCED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DenylistEntry::DISCRIMINATOR, &DENYLIST_ENTRY_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(QuarantinedDonation::DISCRIMINATOR, &QUARANTINED_DONATION_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonationQuarantined::DISCRIMINATOR, &DONATION_QUARANTINED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(QuarantineReleased::DISCRIMINATOR, &QUARANTINE_RELEASED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(DonationsMerged::DISCRIMINATOR, &DONATIONS_MERGED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(FeeExemptionChanged::DISCRIMINATOR, &FEE_EXEMPTION_CHANGED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(PreviewResult::DISCRIMINATOR, &PREVIEW_RESULT_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(MatchReclaimed::DISCRIMINATOR, &MATCH_RECLAIMED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(WaitlistEntry::DISCRIMINATOR, &WAITLIST_ENTRY_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(WaitlistPromoted::DISCRIMINATOR, &WAITLIST_PROMOTED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignPauseChanged::DISCRIMINATOR, &CAMPAIGN_PAUSE_CHANGED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(CampaignCreated::DISCRIMINATOR, &CAMPAIGN_CREATED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(FundsWithdrawn::DISCRIMINATOR, &FUNDS_WITHDRAWN_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(PlatformFeeChanged::DISCRIMINATOR, &PLATFORM_FEE_CHANGED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(AdminTransferProposed::DISCRIMINATOR, &ADMIN_TRANSFER_PROPOSED_DISCRIMINATOR));
+const _: () = assert!(discriminator_matches(AdminTransferred::DISCRIMINATOR, &ADMIN_TRANSFERRED_DISCRIMINATOR));
+
+impl Campaign {
+    /// Fixed size of the Campaign account in bytes
+    /// - 8 bytes for discriminator (Anchor adds this automatically)
+    /// - 1 byte for u8 version
+    /// - 100 + 2 for name (fixed-size bytes plus u16 length)
+    /// - 500 + 2 for description
+    /// - 8 bytes for u64 amount_donated
+    /// - 32 bytes for Pubkey admin
+    /// - 4 bytes for u32 min_seconds_between_donations
+    /// - 8 bytes for u64 donation_count
+    /// - 16 bytes for [u8; 16] idempotency_key
+    /// - 2 bytes for u16 fee_bps
+    /// - 2 * (32 + 2) bytes for fee_recipients
+    /// - 8 bytes for u64 rent_minimum
+    /// - 4 bytes for u32 follower_count
+    /// - 8 bytes for u64 goal
+    /// - 8 bytes for i64 deadline
+    /// - 8 bytes for i64 created_at
+    /// - 8 bytes for i64 refund_delay
+    /// - 8 bytes for u64 refunded_amount
+    /// - 1 byte for CampaignStatus status
+    /// - 8 bytes for i64 reopen_grace_period
+    /// - 8 bytes for i64 cancelled_at
+    /// - 8 bytes for i64 reopen_until
+    /// - 32 bytes for [u8; 32] donor_salt
+    /// - 1 byte for RefundPolicy refund_policy
+    /// - 3 * 32 bytes for allowed_destinations
+    /// - 1 byte for u8 allowed_destination_count
+    /// - 8 bytes for u64 verification_min
+    /// - 8 bytes for u64 verified_raised
+    /// - 4 bytes for u32 allocated_space
+    /// - 8 bytes for u64 match_pool
+    /// - 8 bytes for u64 matched_total
+    /// - 4 bytes for u32 donor_count
+    /// - 1 byte for u8 bump
+    /// - 8 + 2 for currency_symbol (fixed-size bytes plus u16 length)
+    /// - 8 bytes for u64 amount_withdrawn
+    /// - 8 bytes for u64 pledged_total
+    /// - 2 bytes for u16 crank_bounty_bps
+    /// - 4 bytes for u32 min_donors
+    /// - 1 byte for bool require_attestation
+    /// - 32 bytes for Pubkey attestation_issuer
+    /// - 1 byte for bool disputed
+    /// - 4 bytes for u32 max_donors
+    /// - 8 bytes for u64 dust_threshold
+    /// - 8 bytes for i64 withdraw_cliff
+    /// - 32 bytes for rival
+    /// - 32 bytes for proposed_rival
+    /// - 1 byte for bool accrue_fees
+    /// - 2 * 8 bytes for fees_accrued
+    /// - 8 bytes for u64 message_count
+    /// - 1 byte for bool hide_total_until_deadline
+    /// - 8 bytes for u64 withdraw_nonce
+    /// - 1 byte for bool listed
+    /// - 1 byte for EventLevel event_level
+    /// - 8 bytes for u64 donation_maturity_slots
+    /// - 8 bytes for u64 pending_amount
+    /// - 8 bytes for u64 pending_until_slot
+    /// - 32 bytes for creator
+    /// - 1 byte for GoalType goal_type
+    /// - 8 bytes for i64 anti_snipe_window
+    /// - 8 bytes for i64 anti_snipe_extension
+    /// - 8 bytes for u64 hard_goal
+    /// - 32 bytes for withdraw_condition
+    /// - 32 bytes for withdraw_condition_owner
+    /// - 2 bytes for u16 endowment_bps
+    /// - 8 bytes for u64 endowment_total
+    /// - 32 bytes for denylist
+    /// - 8 bytes for u64 quarantined
+    /// - 8 bytes for u64 donation_step
+    /// - 1 byte for bool fee_exempt_campaign
+    /// - 1 byte for DeadlineMode deadline_mode
+    /// - 8 bytes for u64 deadline_slot
+    /// - 32 bytes for rent_sweep_target
+    /// - 2 bytes for locale
+    /// - 32 bytes for match_sponsor
+    /// - 8 bytes for i64 match_expiry
+    /// - MAX_THANK_YOU_LEN + 2 for thank_you (fixed-size bytes plus u16 length)
+    /// - 2 bytes for u16 max_withdraw_pct_per_period
+    /// - 8 bytes for i64 withdraw_period
+    /// - 8 bytes for i64 period_start
+    /// - 8 bytes for u64 withdrawn_in_period
+    /// - 8 bytes for u64 waitlist_next
+    /// - 8 bytes for u64 waitlist_head
+    /// - 4 bytes for u32 promoted_spots
+    /// - 32 bytes for Pubkey pending_admin
+    /// - 8 bytes for u64 recent_pending_amount
+    /// - 8 bytes for u64 recent_pending_until_slot
+    pub const LEN: usize = 8 + 1 + 100 + 2 + 500 + 2 + 8 + 32 + 4 + 8 + 16 + 2 + 2 * (32 + 2) + 8 + 4 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 32 + 1 + 3 * 32 + 1 + 8 + 8 + 4 + 8 + 8 + 4 + 1 + 8 + 2 + 8 + 8 + 2 + 4 + 1 + 32 + 1 + 4 + 8 + 8 + 32 + 32 + 1 + 2 * 8 + 8 + 1 + 8 + 1 + 1 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + 8 + 32 + 32 + 2 + 8 + 32 + 8 + 8 + 1 + 1 + 8 + 32 + 2 + 32 + 8 + MAX_THANK_YOU_LEN + 2 + 2 + 8 + 8 + 8 + 8 + 8 + 4 + 32 + 8 + 8;
+
+    /// Validates `params` and initializes the campaign at `key`, administered by `admin`
+    pub fn init(&mut self, key: Pubkey, params: CampaignParams, admin: Pubkey, data_len: usize, bump: u8) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+
+        // The fee can't exceed the donation and, when charged, must be fully allocated
+        if params.fee_bps > bps::MAX_BPS {
+            return Err(ErrorCode::InvalidFeeBps.into());
+        }
+        if params.fee_bps > 0 && !bps::is_full_split(&params.fee_recipients.map(|r| r.weight_bps)) {
+            return Err(ErrorCode::InvalidFeeSplit.into());
+        }
+
+        if params.deadline != 0 && params.deadline <= now {
+            return Err(ErrorCode::InvalidDeadline.into());
+        }
+        if params.match_expiry < 0 || (params.match_expiry != 0 && params.match_expiry <= now) {
+            return Err(ErrorCode::InvalidMatchExpiry.into());
+        }
+        match params.deadline_mode {
+            DeadlineMode::Timestamp if params.deadline_slot != 0 => return Err(ErrorCode::InvalidDeadlineMode.into()),
+            DeadlineMode::Slot if params.deadline != 0 || params.refund_delay != 0 => {
+                return Err(ErrorCode::InvalidDeadlineMode.into())
+            }
+            DeadlineMode::Slot if params.deadline_slot <= clock.slot => return Err(ErrorCode::InvalidDeadline.into()),
+            _ => {}
+        }
+        if params.goal_type == GoalType::Donors && params.goal == 0 {
+            return Err(ErrorCode::InvalidGoal.into());
+        }
+        if params.hard_goal != 0 && params.hard_goal < params.goal {
+            return Err(ErrorCode::InvalidHardGoal.into());
+        }
+        if params.rent_sweep_target == key {
+            return Err(ErrorCode::InvalidRentSweepTarget.into());
+        }
+        if params.locale != [0; 2] && !params.locale.iter().all(u8::is_ascii_lowercase) {
+            return Err(ErrorCode::InvalidLocale.into());
+        }
+        if (params.withdraw_condition == Pubkey::default()) != (params.withdraw_condition_owner == Pubkey::default()) {
+            return Err(ErrorCode::InvalidWithdrawCondition.into());
+        }
+        if params.hide_total_until_deadline && params.deadline == 0 {
+            return Err(ErrorCode::HiddenTotalWithoutDeadline.into());
+        }
+        if params.withdraw_cliff != 0 && params.withdraw_cliff < now {
+            return Err(ErrorCode::InvalidWithdrawCliff.into());
+        }
+        if params.refund_delay < 0 {
+            return Err(ErrorCode::InvalidRefundDelay.into());
+        }
+        if params.anti_snipe_window < 0
+            || params.anti_snipe_extension < 0
+            || (params.anti_snipe_window > 0 && (params.deadline == 0 || params.anti_snipe_extension == 0))
+        {
+            return Err(ErrorCode::InvalidAntiSnipe.into());
+        }
+        if params.reopen_grace_period < 0 {
+            return Err(ErrorCode::InvalidReopenGracePeriod.into());
+        }
+        if params.allowed_destinations.len() > MAX_ALLOWED_DESTINATIONS {
+            return Err(ErrorCode::TooManyDestinations.into());
+        }
+        if params.extra_space > MAX_EXTRA_SPACE {
+            return Err(ErrorCode::InvalidExtraSpace.into());
+        }
+        if params.endowment_bps > bps::MAX_BPS {
+            return Err(ErrorCode::InvalidEndowmentBps.into());
+        }
+        if params.max_withdraw_pct_per_period > bps::MAX_BPS
+            || params.withdraw_period < 0
+            || (params.max_withdraw_pct_per_period > 0 && params.withdraw_period == 0)
+        {
+            return Err(ErrorCode::InvalidWithdrawLimit.into());
+        }
+        if params.crank_bounty_bps > MAX_CRANK_BOUNTY_BPS {
+            return Err(ErrorCode::InvalidCrankBounty.into());
+        }
+        if params.require_attestation && params.attestation_issuer == Pubkey::default() {
+            return Err(ErrorCode::InvalidAttestationIssuer.into());
+        }
+
+        self.version = CAMPAIGN_VERSION;
+        self.set_name(&params.name)?;
+        self.set_description(&params.description)?;
+        self.set_thank_you(&params.thank_you)?;
+        self.max_withdraw_pct_per_period = params.max_withdraw_pct_per_period;
+        self.withdraw_period = params.withdraw_period;
+        self.period_start = 0;
+        self.withdrawn_in_period = 0;
+        self.waitlist_next = 0;
+        self.waitlist_head = 0;
+        self.promoted_spots = 0;
+        self.pending_admin = Pubkey::default();
+        self.recent_pending_amount = 0;
+        self.recent_pending_until_slot = 0;
+        self.set_currency_symbol(match params.currency_symbol.as_str() {
+            "" => DEFAULT_CURRENCY_SYMBOL,
+            symbol => symbol,
+        })?;
+        self.amount_donated = 0;
+        self.admin = admin;
+        self.min_seconds_between_donations = params.min_seconds_between_donations;
+        self.donation_count = 0;
+        self.idempotency_key = params.idempotency_key;
+        self.fee_bps = params.fee_bps;
+        self.fee_recipients = params.fee_recipients;
+        self.goal = params.goal;
+        self.deadline = params.deadline;
+        self.created_at = now;
+        self.refund_delay = params.refund_delay;
+        self.refunded_amount = 0;
+        self.status = CampaignStatus::Active;
+        self.reopen_grace_period = params.reopen_grace_period;
+        self.cancelled_at = 0;
+        self.reopen_until = 0;
+        self.refund_policy = params.refund_policy;
+        // The allowlist is only ever written here, so it can't change after creation
+        self.allowed_destinations = [Pubkey::default(); MAX_ALLOWED_DESTINATIONS];
+        self.allowed_destinations[..params.allowed_destinations.len()].copy_from_slice(&params.allowed_destinations);
+        self.allowed_destination_count = params.allowed_destinations.len() as u8;
+        self.verification_min = params.verification_min;
+        self.verified_raised = 0;
+        self.allocated_space = data_len as u32;
+        self.match_pool = 0;
+        self.matched_total = 0;
+        self.donor_count = 0;
+        self.bump = bump;
+        self.amount_withdrawn = 0;
+        self.pledged_total = 0;
+        self.crank_bounty_bps = params.crank_bounty_bps;
+        self.min_donors = params.min_donors;
+        self.require_attestation = params.require_attestation;
+        self.attestation_issuer = params.attestation_issuer;
+        self.disputed = false;
+        self.max_donors = params.max_donors;
+        self.dust_threshold = params.dust_threshold;
+        self.withdraw_cliff = params.withdraw_cliff;
+        self.rival = Pubkey::default();
+        self.proposed_rival = Pubkey::default();
+        self.accrue_fees = params.accrue_fees;
+        self.fees_accrued = [0; 2];
+        self.message_count = 0;
+        self.hide_total_until_deadline = params.hide_total_until_deadline;
+        self.withdraw_nonce = 0;
+        self.listed = params.listed;
+        self.event_level = params.event_level;
+        self.donation_maturity_slots = params.donation_maturity_slots;
+        self.pending_amount = 0;
+        self.pending_until_slot = 0;
+        self.creator = admin;
+        self.goal_type = params.goal_type;
+        self.anti_snipe_window = params.anti_snipe_window;
+        self.anti_snipe_extension = params.anti_snipe_extension;
+        self.hard_goal = params.hard_goal;
+        self.withdraw_condition = params.withdraw_condition;
+        self.withdraw_condition_owner = params.withdraw_condition_owner;
+        self.endowment_bps = params.endowment_bps;
+        self.endowment_total = 0;
+        self.denylist = params.denylist;
+        self.quarantined = 0;
+        self.donation_step = params.donation_step;
+        self.fee_exempt_campaign = false;
+        self.deadline_mode = params.deadline_mode;
+        self.deadline_slot = params.deadline_slot;
+        self.rent_sweep_target = params.rent_sweep_target;
+        self.locale = params.locale;
+        self.match_sponsor = Pubkey::default();
+        self.match_expiry = params.match_expiry;
+        // Salt donor hashes per campaign so the same wallet can't be linked across campaigns
+        self.donor_salt = hashv(&[b"donor-salt", key.as_ref(), &clock.slot.to_le_bytes()]).to_bytes();
+        // Cache the rent-exempt minimum so withdrawals don't need the Rent sysvar
+        self.rent_minimum = Rent::get()?.minimum_balance(data_len);
+        Ok(())
+    }
+
+    /// Lamports the admin can never take out: the rent-exempt minimum, the unspent match pool,
+    /// fees not yet claimed by their recipients and quarantined donations
+    pub fn reserved_lamports(&self) -> u64 {
+        // Saturates rather than wrapping, so an overflow can only over-reserve
+        self.rent_minimum
+            .saturating_add(self.match_pool)
+            .saturating_add(self.fees_accrued_total())
+            .saturating_add(self.quarantined)
+    }
+
+    /// Lamports of the campaign's `balance` that can be paid out, above its reserved lamports
+    ///
+    /// Errors instead of underflowing if the account was drained below its rent floor.
+    pub fn available_lamports(&self, balance: u64) -> Result<u64> {
+        if balance < self.rent_minimum {
+            return Err(ErrorCode::AccountNotRentExempt.into());
+        }
+        Ok(balance.checked_sub(self.reserved_lamports()).ok_or(ErrorCode::InsufficientFunds)?)
+    }
+
+    /// Donated lamports still pending at `slot` (0 once they have matured)
+    pub fn pending_lamports(&self, slot: u64) -> u64 {
+        let mut pending = 0u64;
+        if slot < self.pending_until_slot {
+            pending = pending.saturating_add(self.pending_amount);
+        }
+        if slot < self.recent_pending_until_slot {
+            pending = pending.saturating_add(self.recent_pending_amount);
+        }
+        pending
+    }
+
+    /// Drops the pending buckets that have matured by `slot`, moving the recent bucket up
+    /// once the older one is done
+    fn settle_pending(&mut self, slot: u64) {
+        if slot >= self.recent_pending_until_slot {
+            self.recent_pending_amount = 0;
+        }
+        if slot >= self.pending_until_slot {
+            self.pending_amount = self.recent_pending_amount;
+            self.pending_until_slot = self.recent_pending_until_slot;
+            self.recent_pending_amount = 0;
+        }
+    }
+
+    /// Records `amount` refunded to a donor, releasing the endowed share of it
+    ///
+    /// A refunded gift no longer belongs to the campaign, so neither does the part of it that
+    /// went into the endowment; this is how the endowment of a failed or cancelled campaign
+    /// flows back to its donors.
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.refunded_amount = self.refunded_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        let endowed = bps::apply(amount, self.endowment_bps).ok_or(ErrorCode::InvalidEndowmentBps)?;
+        // Rounding per donation can leave a little more endowed than the share of the sum
+        self.endowment_total = self.endowment_total.saturating_sub(endowed);
+        Ok(())
+    }
+
+    /// Lamports of the campaign's `balance` the admin can move out at `slot`: its available
+    /// lamports less donations that are still pending and the endowment
+    pub fn spendable_lamports(&self, balance: u64, slot: u64) -> Result<u64> {
+        Ok(self
+            .available_lamports(balance)?
+            .saturating_sub(self.pending_lamports(slot))
+            .saturating_sub(self.endowment_total))
+    }
+
+    /// The seed the idempotency key contributes to the campaign's address (empty without a key)
+    pub fn seed_key(&self) -> &[u8] {
+        croudfunding_types::campaign_seed_key(&self.idempotency_key)
+    }
+
+    /// Returns true if withdrawals pay the platform fee
+    ///
+    /// A campaign with an allowlist doesn't: it committed at creation to paying only the listed
+    /// addresses, and the treasury can be changed by the config authority at any time.
+    pub fn owes_platform_fee(&self) -> bool {
+        !self.fee_exempt_campaign && self.allowed_destination_count == 0
+    }
+
+    /// Platform fee on a gross donation of `amount` lamports (0 for a fee-exempt campaign)
+    pub fn donation_fee(&self, amount: u64) -> Result<u64> {
+        if self.fee_exempt_campaign {
+            return Ok(0);
+        }
+        Ok(bps::apply(amount, self.fee_bps).ok_or(ErrorCode::InvalidFeeBps)?)
+    }
+
+    /// Splits the fee on `amount` between the fee recipients, in their order
+    ///
+    /// Nothing is split when no fee is owed, so a campaign without a fee may leave its
+    /// recipients unset.
+    pub fn fee_shares(&self, amount: u64) -> Result<[u64; 2]> {
+        let fee = self.donation_fee(amount)?;
+        if fee == 0 {
+            return Ok([0; 2]);
+        }
+        Ok(bps::split(fee, self.fee_recipients.map(|r| r.weight_bps)).ok_or(ErrorCode::InvalidFeeSplit)?)
+    }
+
+    /// Fees held in the campaign for its recipients (in lamports)
+    pub fn fees_accrued_total(&self) -> u64 {
+        self.fees_accrued.iter().fold(0, |total, fee| total.saturating_add(*fee))
+    }
+
+    /// Adds a donation of `amount` net lamports to the totals and matches it from the sponsor pool
+    ///
+    /// `first_donation` is true when the donor has no donation on record yet. Returns the
+    /// matched amount, which a pool running low caps at what's left in it.
+    ///
+    /// `endowment_bps` of the donation is set aside in the endowment.
+    ///
+    /// With `donation_maturity_slots` set, the donation is held as pending until that many
+    /// slots after `slot`. The oldest pending donations keep their own schedule; later ones
+    /// share a second bucket that each new donation pushes back, until the first bucket
+    /// matures and the second takes its place. A donation therefore matures within twice the
+    /// maturity period, however often others arrive.
+    pub fn record_donation(&mut self, amount: u64, first_donation: bool, slot: u64) -> Result<u64> {
+        self.amount_donated = self.amount_donated.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        let endowed = bps::apply(amount, self.endowment_bps).ok_or(ErrorCode::InvalidEndowmentBps)?;
+        self.endowment_total = self.endowment_total.checked_add(endowed).ok_or(ErrorCode::Overflow)?;
+        if self.donation_maturity_slots > 0 {
+            self.settle_pending(slot);
+            let until_slot = slot.saturating_add(self.donation_maturity_slots);
+            if self.pending_amount == 0 {
+                self.pending_amount = amount;
+                self.pending_until_slot = until_slot;
+            } else {
+                self.recent_pending_amount = self.recent_pending_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+                self.recent_pending_until_slot = until_slot;
+            }
+        }
+        self.donation_count = self.donation_count.saturating_add(1);
+        if first_donation {
+            self.donor_count = self.donor_count.saturating_add(1);
+            // Only a wallet's first donation, and only above the dust threshold, counts as verified
+            if amount >= self.verification_min {
+                self.verified_raised = self.verified_raised.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+            }
+        }
+
+        let matched = amount.min(self.match_pool);
+        self.match_pool = self.match_pool.checked_sub(matched).ok_or(ErrorCode::Overflow)?;
+        self.matched_total = self.matched_total.checked_add(matched).ok_or(ErrorCode::Overflow)?;
+        self.amount_donated = self.amount_donated.checked_add(matched).ok_or(ErrorCode::Overflow)?;
+        Ok(matched)
+    }
+
+    /// Lamports the account should hold according to its bookkeeping:
+    ///
+    /// `rent_minimum + amount_donated + match_pool + fees_accrued + quarantined - amount_withdrawn - refunded_amount`
+    ///
+    /// `amount_donated` already includes matched funds, which came out of sponsor deposits
+    /// (`match_pool + matched_total`), so only the unspent pool is added on top; matched funds
+    /// returned to the sponsor count as refunded. Signed, so a
+    /// corrupted ledger shows up as drift rather than an overflow.
+    pub fn expected_balance(&self) -> i128 {
+        i128::from(self.rent_minimum) + i128::from(self.amount_donated) + i128::from(self.match_pool)
+            + i128::from(self.fees_accrued_total())
+            + i128::from(self.quarantined)
+            - i128::from(self.amount_withdrawn)
+            - i128::from(self.refunded_amount)
+    }
+
+    /// Amount raised net of refunds (in lamports)
+    pub fn net_raised(&self) -> u64 {
+        // Refunds never exceed donations, but a broken invariant reads as 0 rather than panicking
+        self.amount_donated.saturating_sub(self.refunded_amount)
+    }
+
+    /// Returns true while the campaign keeps its running total out of events and return data
+    ///
+    /// Only the public surface is gated; the stored totals stay exact for accounting.
+    pub fn total_hidden(&self, now: i64) -> bool {
+        self.hide_total_until_deadline && now < self.deadline
+    }
+
+    /// What `goal` is measured against: net lamports raised or distinct donors, per `goal_type`
+    pub fn goal_progress(&self) -> u64 {
+        match self.goal_type {
+            GoalType::Amount => self.net_raised(),
+            GoalType::Donors => u64::from(self.donor_count),
+        }
+    }
+
+    /// Returns true if the campaign has a goal and its progress meets it
+    ///
+    /// `goal` is the soft goal: meeting it is what makes the campaign successful.
+    pub fn goal_reached(&self) -> bool {
+        self.goal > 0 && self.goal_progress() >= self.goal
+    }
+
+    /// Returns true if the campaign has a stretch goal and its progress meets it
+    pub fn hard_goal_reached(&self) -> bool {
+        self.hard_goal > 0 && self.goal_progress() >= self.hard_goal
+    }
+
+    /// Weights `amount` by how early in the campaign `now` is, for early-backer rankings
+    ///
+    /// Returns `amount * (deadline - now) / (deadline - created_at)`, so a donation at creation
+    /// counts in full and one just before the deadline barely counts. Campaigns without a
+    /// deadline (or with a zero-length one) weight every donation 1.
+    pub fn time_weighted(&self, amount: u64, now: i64) -> u64 {
+        let duration = self.deadline.saturating_sub(self.created_at);
+        if self.deadline == 0 || duration <= 0 {
+            return amount;
+        }
+        let remaining = self.deadline.saturating_sub(now).clamp(0, duration);
+        // The quotient never exceeds `amount` since remaining <= duration
+        (u128::from(amount) * remaining as u128 / duration as u128) as u64
+    }
+
+    /// Checks that `donor` may donate, given the attestation passed in (if any)
+    ///
+    /// Campaigns without `require_attestation` accept everyone. Gated campaigns need an
+    /// unexpired attestation of `donor` by the campaign's issuer.
+    pub fn check_attestation(&self, attestation: Option<&Attestation>, donor: &Pubkey, now: i64) -> Result<()> {
+        if !self.require_attestation {
+            return Ok(());
+        }
+        let attestation = attestation.ok_or(ErrorCode::AttestationRequired)?;
+        if attestation.issuer != self.attestation_issuer
+            || attestation.subject != *donor
+            || (attestation.expires_at != 0 && now >= attestation.expires_at)
+        {
+            return Err(ErrorCode::AttestationInvalid.into());
+        }
+        Ok(())
+    }
+
+    /// Returns whether the donor is flagged on the campaign's denylist, given their entry address
+    ///
+    /// Campaigns without a `denylist` flag no one. Screened campaigns need the donor's
+    /// `DenylistEntry` address (its seeds are checked by the caller); the donor is flagged
+    /// if an entry exists there, so an unflagged donor passes an empty account.
+    pub fn check_denylist(&self, entry: Option<&AccountInfo>) -> Result<bool> {
+        if self.denylist == Pubkey::default() {
+            return Ok(false);
+        }
+        let entry = entry.ok_or(ErrorCode::DenylistEntryRequired)?;
+        Ok(*entry.owner == crate::ID && !entry.data_is_empty())
+    }
+
+    /// Checks that `amount` (before fees) is a whole number of the campaign's donation steps
+    pub fn check_donation_step(&self, amount: u64) -> Result<()> {
+        if self.donation_step != 0 && !amount.is_multiple_of(self.donation_step) {
+            return Err(ErrorCode::InvalidDonationIncrement.into());
+        }
+        Ok(())
+    }
+
+    /// Returns true if a new donor can join without going over `max_donors`, counting spots
+    /// held for promoted waitlisters as taken
+    pub fn has_free_spot(&self) -> bool {
+        self.max_donors == 0 || self.donor_count.saturating_add(self.promoted_spots) < self.max_donors
+    }
+
+    /// Checks that a donor may give; `first_donation` is true for donors without a donation on record
+    ///
+    /// Once the campaign has no free spot, only existing donors can top up.
+    pub fn check_donor_capacity(&self, first_donation: bool) -> Result<()> {
+        if first_donation && !self.has_free_spot() {
+            return Err(ErrorCode::DonorCapReached.into());
+        }
+        Ok(())
+    }
+
+    /// Wallet reclaimed rent of the campaign's auxiliary accounts goes to
+    pub fn rent_sweep_target(&self) -> Pubkey {
+        if self.rent_sweep_target == Pubkey::default() {
+            self.admin
+        } else {
+            self.rent_sweep_target
+        }
+    }
+
+    /// Returns true if the sponsor may reclaim the unused match pool at `now` and `slot`
+    ///
+    /// Without a `match_expiry`, the pool expires once the campaign stops taking donations.
+    pub fn match_expired(&self, now: i64, slot: u64) -> bool {
+        if self.match_expiry != 0 {
+            return now >= self.match_expiry;
+        }
+        self.donation_block_reason(now, slot).is_some()
+    }
+
+    /// Returns true if the campaign has a deadline, in either mode
+    pub fn has_deadline(&self) -> bool {
+        match self.deadline_mode {
+            DeadlineMode::Timestamp => self.deadline != 0,
+            DeadlineMode::Slot => self.deadline_slot != 0,
+        }
+    }
+
+    /// Returns true if the campaign's deadline has passed at `now` (or `slot`, in Slot mode)
+    pub fn deadline_passed(&self, now: i64, slot: u64) -> bool {
+        match self.deadline_mode {
+            DeadlineMode::Timestamp => self.deadline != 0 && now >= self.deadline,
+            DeadlineMode::Slot => self.deadline_slot != 0 && slot >= self.deadline_slot,
+        }
+    }
+
+    /// Derives the campaign's status from its deadline and success conditions
+    ///
+    /// The single place an active campaign ends: once the deadline has passed it becomes
+    /// `Successful` or `Failed`. `Cancelled` is only left through `reopen_campaign`, `Paused`
+    /// through `resume_campaign` (a paused campaign doesn't end until resumed), and the
+    /// outcome of an ended campaign is never revisited. Returns true if the campaign has just
+    /// ended, so the caller can emit `CampaignFinalized`.
+    pub fn recompute_status(&mut self, now: i64, slot: u64) -> bool {
+        if self.status != CampaignStatus::Active || !self.deadline_passed(now, slot) {
+            return false;
+        }
+        self.status = if self.is_successful() {
+            CampaignStatus::Successful
+        } else {
+            CampaignStatus::Failed
+        };
+        true
+    }
+
+    /// Pushes the deadline out by `anti_snipe_extension` if `now` falls within `anti_snipe_window`
+    /// of it, never past `MAX_DURATION` after creation
+    ///
+    /// Returns the previous deadline if it moved.
+    pub fn extend_for_late_donation(&mut self, now: i64) -> Option<i64> {
+        if self.anti_snipe_window == 0 || self.deadline == 0 || self.deadline.saturating_sub(now) > self.anti_snipe_window {
+            return None;
+        }
+        let extended = self
+            .deadline
+            .saturating_add(self.anti_snipe_extension)
+            .min(self.created_at.saturating_add(MAX_DURATION));
+        if extended <= self.deadline {
+            return None;
+        }
+        let previous = self.deadline;
+        self.deadline = extended;
+        Some(previous)
+    }
+
+    /// Returns true if the campaign meets its success conditions: its goal (if any) and its
+    /// minimum number of distinct donors
+    pub fn is_successful(&self) -> bool {
+        self.goal_progress() >= self.goal && self.donor_count >= self.min_donors
+    }
+
+    /// Why the campaign can't take donations at `now` and `slot`, or `None` if it can
+    ///
+    /// The deadline is checked directly rather than trusting `status`, so donations landing
+    /// between the deadline and `finalize` can't skew the final totals.
+    pub fn donation_block_reason(&self, now: i64, slot: u64) -> Option<&'static str> {
+        match self.status {
+            CampaignStatus::Active if self.deadline_passed(now, slot) => Some("deadline has passed"),
+            CampaignStatus::Active => None,
+            CampaignStatus::Cancelled => Some("campaign is cancelled"),
+            CampaignStatus::Paused => Some("campaign is paused"),
+            CampaignStatus::Successful | CampaignStatus::Failed => Some("campaign is finalized"),
+        }
+    }
+
+    /// Checks that the campaign takes donations at `now` and `slot`, logging why not otherwise
+    ///
+    /// The single gate of every path that brings funds in (donations, pledges, match pool
+    /// deposits and transfers from other campaigns); each calls it before moving any funds.
+    pub fn check_accepting_donations(&self, now: i64, slot: u64) -> Result<()> {
+        if let Some(reason) = self.donation_block_reason(now, slot) {
+            msg!("Cannot accept donations: {}", reason);
+            return Err(ErrorCode::CannotAcceptDonations.into());
+        }
+        Ok(())
+    }
+
+    /// Returns true if donors are refunded should the campaign miss its goal or donor minimum by the deadline.
+    /// Such a campaign's funds stay reserved until it meets those conditions.
+    pub fn is_all_or_nothing(&self) -> bool {
+        self.refund_policy == RefundPolicy::OnFailure && self.has_target()
+    }
+
+    /// Returns true if the campaign never took a donation, pledge or sponsor match, so
+    /// nobody but the admin has lamports in it
+    pub fn is_unfunded(&self) -> bool {
+        self.amount_donated == 0
+            && self.pledged_total == 0
+            && self.quarantined == 0
+            && self.match_pool == 0
+            && self.matched_total == 0
+    }
+
+    /// Returns true if the campaign has a goal or donor minimum to meet by a deadline
+    pub fn has_target(&self) -> bool {
+        (self.goal > 0 || self.min_donors > 0) && self.has_deadline()
+    }
+
+    /// Checks that the admin may move funds out of the campaign at `now` and `slot`
+    ///
+    /// A campaign with a target pays out once it meets its success conditions or its deadline
+    /// has passed; an open-ended one any time. Funds donors may still claim back stay
+    /// reserved: an all-or-nothing campaign's until it succeeds, and a cancelled campaign's
+    /// unless it has no refund policy.
+    pub fn check_withdrawable(&self, now: i64, slot: u64) -> Result<()> {
+        // The cliff holds regardless of status, even once the goal is met
+        if now < self.withdraw_cliff {
+            return Err(ErrorCode::CliffNotReached.into());
+        }
+        match self.status {
+            CampaignStatus::Successful => Ok(()),
+            CampaignStatus::Active if !self.has_target() || self.is_successful() => Ok(()),
+            // Past the deadline but not yet finalized: only an all-or-nothing campaign may still fail into refunds
+            CampaignStatus::Active if self.deadline_passed(now, slot) && !self.is_all_or_nothing() => Ok(()),
+            CampaignStatus::Active => Err(ErrorCode::FundsReserved.into()),
+            CampaignStatus::Paused => Err(ErrorCode::CampaignPaused.into()),
+            CampaignStatus::Failed if self.refund_policy != RefundPolicy::OnFailure => Ok(()),
+            CampaignStatus::Cancelled if self.refund_policy == RefundPolicy::None => Ok(()),
+            // The rest of the funds of cancelled and failed campaigns are reserved for refunds
+            CampaignStatus::Cancelled | CampaignStatus::Failed => Err(ErrorCode::CampaignNotActive.into()),
+        }
+    }
+
+    /// Lamports raised above an amount goal that haven't been withdrawn yet (0 for donor-count
+    /// goals and without a goal, where every lamport may still be owed back to donors)
+    pub fn overage(&self) -> u64 {
+        if self.goal_type != GoalType::Amount || self.goal == 0 {
+            return 0;
+        }
+        self.net_raised().saturating_sub(self.goal).saturating_sub(self.amount_withdrawn)
+    }
+
+    /// Checks that the admin may withdraw `amount` of the overage at `now`
+    ///
+    /// Unlike `check_withdrawable`, an active all-or-nothing campaign may release its surplus
+    /// early; the goal itself stays locked until the campaign succeeds.
+    pub fn check_overage_withdrawable(&self, now: i64, amount: u64) -> Result<()> {
+        if now < self.withdraw_cliff {
+            return Err(ErrorCode::CliffNotReached.into());
+        }
+        if matches!(self.status, CampaignStatus::Cancelled | CampaignStatus::Failed) {
+            return Err(ErrorCode::CampaignNotActive.into());
+        }
+        if self.status == CampaignStatus::Paused {
+            return Err(ErrorCode::CampaignPaused.into());
+        }
+        let overage = self.overage();
+        if overage == 0 {
+            return Err(ErrorCode::NoOverage.into());
+        }
+        if amount > overage {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+        Ok(())
+    }
+
+    /// Counts `amount` moved out at `now` against the per-period withdrawal cap
+    ///
+    /// A period starts with the first outflow after the previous one ended and lasts
+    /// `withdraw_period` seconds; within it, outflows may add up to
+    /// `max_withdraw_pct_per_period` of `amount_donated`.
+    pub fn record_period_withdrawal(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.max_withdraw_pct_per_period == 0 {
+            return Ok(());
+        }
+        if now >= self.period_start.saturating_add(self.withdraw_period) {
+            self.period_start = now;
+            self.withdrawn_in_period = 0;
+        }
+        let cap = bps::apply(self.amount_donated, self.max_withdraw_pct_per_period).ok_or(ErrorCode::InvalidWithdrawLimit)?;
+        let withdrawn = self.withdrawn_in_period.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        if withdrawn > cap {
+            return Err(ErrorCode::PeriodLimitExceeded.into());
+        }
+        self.withdrawn_in_period = withdrawn;
+        Ok(())
+    }
+
+    /// Checks the campaign's withdrawal condition, if it has one
+    ///
+    /// `condition` must be the configured account, owned by the trusted program, with a
+    /// non-zero first byte.
+    pub fn check_withdraw_condition(&self, condition: Option<&AccountInfo>) -> Result<()> {
+        if self.withdraw_condition == Pubkey::default() {
+            return Ok(());
+        }
+        let condition = condition.ok_or(ErrorCode::ConditionNotMet)?;
+        if *condition.key != self.withdraw_condition || *condition.owner != self.withdraw_condition_owner {
+            return Err(ErrorCode::ConditionNotMet.into());
+        }
+        match condition.try_borrow_data()?.first() {
+            Some(flag) if *flag != 0 => Ok(()),
+            _ => Err(ErrorCode::ConditionNotMet.into()),
+        }
+    }
+
+    /// Checks that the refund policy allows donors to claim refunds at `now` and `slot`
+    ///
+    /// This is the single source of truth for every refund path. A campaign finalized as
+    /// `Successful` never refunds, whatever its live totals say now. Otherwise:
+    /// - `None`: never
+    /// - `OnFailure`: once cancelled, or after `deadline + refund_delay` if the goal was missed
+    /// - `UntilDeadline`: once cancelled, or any time before the deadline
+    ///
+    /// Slot deadlines have no refund delay, so refunds open at the deadline slot.
+    pub fn check_refund_allowed(&self, now: i64, slot: u64) -> Result<()> {
+        if self.status == CampaignStatus::Successful {
+            return Err(ErrorCode::RefundNotAvailable.into());
+        }
+        let cancelled = self.status == CampaignStatus::Cancelled;
+        match self.refund_policy {
+            RefundPolicy::None => Err(ErrorCode::RefundNotAvailable.into()),
+            RefundPolicy::OnFailure => {
+                if cancelled {
+                    return Ok(());
+                }
+                // Only campaigns with a deadline that missed their success conditions refund donors
+                if !self.has_deadline() || self.is_successful() {
+                    return Err(ErrorCode::RefundNotAvailable.into());
+                }
+                // Give the creator a buffer after the deadline before refunds open
+                let opened = match self.deadline_mode {
+                    DeadlineMode::Timestamp => now >= self.deadline.saturating_add(self.refund_delay),
+                    DeadlineMode::Slot => self.deadline_passed(now, slot),
+                };
+                if !opened {
+                    return Err(ErrorCode::RefundNotYetOpen.into());
+                }
+                Ok(())
+            }
+            RefundPolicy::UntilDeadline => {
+                if cancelled || !self.deadline_passed(now, slot) {
+                    Ok(())
+                } else {
+                    Err(ErrorCode::RefundNotAvailable.into())
+                }
+            }
+        }
+    }
+
+    /// Checks that funds may leave the campaign to `destination`
+    pub fn check_destination(&self, destination: &Pubkey) -> Result<()> {
+        let allowed = &self.allowed_destinations[..usize::from(self.allowed_destination_count)];
+        if !allowed.is_empty() && !allowed.contains(destination) {
+            return Err(ErrorCode::DestinationNotAllowed.into());
+        }
+        Ok(())
+    }
+
+    /// Pseudonymous identifier of `donor` within this campaign
+    pub fn donor_hash(&self, donor: &Pubkey) -> [u8; 32] {
+        hashv(&[&self.donor_salt, donor.as_ref()]).to_bytes()
+    }
+
+    /// Returns the campaign name
+    pub fn name(&self) -> &str {
+        croudfunding_types::padded_str(&self.name, self.name_len).unwrap_or_default()
+    }
+
+    /// Sets the campaign name, zero-padding the unused bytes
+    pub fn set_name(&mut self, name: &str) -> Result<()> {
+        self.name_len = croudfunding_types::write_padded(&mut self.name, name).ok_or(ErrorCode::NameTooLong)?;
+        Ok(())
+    }
+
+    /// Returns the campaign description
+    pub fn description(&self) -> &str {
+        croudfunding_types::padded_str(&self.description, self.description_len).unwrap_or_default()
+    }
+
+    /// Sets the campaign description, zero-padding the unused bytes
+    pub fn set_description(&mut self, description: &str) -> Result<()> {
+        self.description_len = croudfunding_types::write_padded(&mut self.description, description)
+            .ok_or(ErrorCode::DescriptionTooLong)?;
+        Ok(())
+    }
+
+    /// Returns the creator's thank-you message (empty if none)
+    pub fn thank_you(&self) -> &str {
+        croudfunding_types::padded_str(&self.thank_you, self.thank_you_len).unwrap_or_default()
+    }
+
+    /// Sets the thank-you message, zero-padding the unused bytes
+    pub fn set_thank_you(&mut self, thank_you: &str) -> Result<()> {
+        self.thank_you_len = croudfunding_types::write_padded(&mut self.thank_you, thank_you)
+            .ok_or(ErrorCode::ThankYouTooLong)?;
+        Ok(())
+    }
+
+    /// Returns the currency symbol amounts are displayed with
+    pub fn currency_symbol(&self) -> &str {
+        croudfunding_types::padded_str(&self.currency_symbol, self.currency_symbol_len).unwrap_or_default()
+    }
+
+    /// Sets the currency symbol, zero-padding the unused bytes
+    pub fn set_currency_symbol(&mut self, symbol: &str) -> Result<()> {
+        self.currency_symbol_len = croudfunding_types::write_padded(&mut self.currency_symbol, symbol)
+            .ok_or(ErrorCode::SymbolTooLong)?;
+        Ok(())
+    }
+}
+
+/// Arguments of `create`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CampaignParams {
+    pub name: String,           // The name of the campaign
+    pub description: String,    // A short description of the campaign
+    pub min_seconds_between_donations: u32, // Minimum delay between two donations from the same wallet (0 = no limit)
+    pub idempotency_key: [u8; 16], // Client key in the campaign address so retries resolve to the same account (all zero = none, one campaign per wallet)
+    pub fee_bps: u16,           // Platform fee taken from each donation, in basis points (0 = no fee)
+    pub fee_recipients: [FeeRecipient; 2], // Wallets sharing the fee, with weights in basis points summing to 10000
+    pub goal: u64,              // Funding goal in lamports, or in donors if goal_type is Donors (0 = no goal)
+    pub deadline: i64,          // Unix timestamp the campaign ends at (0 = no deadline)
+    pub refund_delay: i64,      // Seconds after the deadline before donors of a failed campaign can claim refunds
+    pub reopen_grace_period: i64, // Seconds after a cancellation during which the admin may reopen
+    pub refund_policy: RefundPolicy, // Refunds offered to donors (changeable until the first donation)
+    pub allowed_destinations: Vec<Pubkey>, // Up to 3 addresses funds may ever be withdrawn to (empty = any)
+    pub verification_min: u64,  // Minimum first donation from a wallet that counts toward verified_raised
+    pub extra_space: u16,       // Bytes allocated beyond Campaign::LEN so later layout growth needs no realloc (max MAX_EXTRA_SPACE)
+    pub currency_symbol: String, // Symbol clients display amounts with, at most 8 bytes (empty = "SOL")
+    pub crank_bounty_bps: u16,  // Share of each cranked refund paid to the cranker, in basis points (max MAX_CRANK_BOUNTY_BPS)
+    pub min_donors: u32,        // Distinct donors required, on top of the goal, for the campaign to succeed (0 = no minimum)
+    pub require_attestation: bool, // Only admit donors holding an attestation from attestation_issuer
+    pub attestation_issuer: Pubkey, // Issuer trusted to attest donors (required if require_attestation)
+    pub max_donors: u32,        // Only the first max_donors distinct donors may give (0 = unlimited)
+    pub dust_threshold: u64,    // Refundable balance small enough to be swept to the admin on close (0 = none)
+    pub withdraw_cliff: i64,    // No funds leave the campaign before this Unix timestamp (0 = no cliff; otherwise not before creation)
+    pub accrue_fees: bool,      // Hold fees in the campaign until each recipient claims them, rather than paying them per donation
+    pub hide_total_until_deadline: bool, // Publish the running total only once the deadline passes (requires a deadline)
+    pub listed: bool,           // Show the campaign in public listings; unlisted campaigns are reachable by address only
+    pub event_level: EventLevel, // Which event each donation emits (full, minimal or none)
+    pub donation_maturity_slots: u64, // Slots each donation is held as pending before the admin can spend it (0 = no hold)
+    pub goal_type: GoalType,    // Whether goal counts lamports raised or distinct donors (a donor goal can't be 0)
+    pub anti_snipe_window: i64, // Seconds before the deadline in which a donation extends it (0 = never; requires a deadline)
+    pub anti_snipe_extension: i64, // Seconds each such donation adds to the deadline, up to MAX_DURATION after creation
+    pub hard_goal: u64,         // Stretch goal in goal's unit, at least goal (0 = none); goal is the soft goal deciding success
+    pub withdraw_condition: Pubkey, // Account whose first byte must be non-zero for withdraw to succeed (default = unconditional)
+    pub withdraw_condition_owner: Pubkey, // Program that must own withdraw_condition (required with it)
+    pub endowment_bps: u16,     // Share of each donation kept permanently in the endowment, in basis points (0 = none)
+    pub denylist: Pubkey,       // Denylist authority whose flagged wallets' donations are quarantined (default = no screening)
+    pub donation_step: u64,     // Unit price donations must be a multiple of, in lamports (0 = any amount)
+    pub deadline_mode: DeadlineMode, // Timestamp: the campaign ends at deadline; Slot: at deadline_slot (deadline must be 0)
+    pub deadline_slot: u64,     // Slot the campaign ends at, required in Slot mode and 0 otherwise; excludes refund_delay
+    pub rent_sweep_target: Pubkey, // Wallet that receives rent reclaimed by close_aux (default = the admin)
+    pub locale: [u8; 2],        // ISO 639-1 language code in lowercase ASCII letters, e.g. b"en" ([0; 2] = unspecified)
+    pub match_expiry: i64,      // Unix timestamp after which a sponsor may reclaim the unused match pool (0 = once the campaign ends)
+    pub thank_you: String,      // Thank-you message shown to donors after donating, at most 280 bytes (empty = none)
+    pub max_withdraw_pct_per_period: u16, // Cap on outflows per period, in basis points of amount_donated (0 = no cap)
+    pub withdraw_period: i64,   // Length of a withdrawal period in seconds (required with the cap)
+}
+
+/// Original campaign layout, deployed before `version` and fixed-size text fields were introduced
+#[derive(AnchorDeserialize)]
+pub struct LegacyCampaign {
+    pub name: String,           // Campaign name, at most 100 bytes
+    pub description: String,    // Campaign description, at most 500 bytes
+    pub amount_donated: u64,    // Total amount donated (in lamports)
+    pub admin: Pubkey,          // Admin (creator) of the campaign
+}
+
+impl LegacyCampaign {
+    /// Size every legacy campaign account was allocated with
+    /// - 8 bytes for discriminator
+    /// - 4 + 100 for name
+    /// - 4 + 500 for description
+    /// - 8 bytes for u64 amount_donated
+    /// - 32 bytes for Pubkey admin
+    pub const LEN: usize = 8 + 4 + 100 + 4 + 500 + 8 + 32;
+}
+
+impl DonorRecord {
+    /// Fixed size of the DonorRecord account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 32 bytes for Pubkey donor
+    /// - 8 bytes for u64 amount_donated
+    /// - 8 bytes for u64 donation_count
+    /// - 8 bytes for i64 last_donated_at
+    /// - 1 byte for u8 bump
+    /// - 32 bytes for [u8; 32] donor_hash
+    /// - 1 byte for bool hide_amount
+    /// - 8 bytes for u64 weighted_score
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 32 + 1 + 8;
+
+    /// Checks that at least `min_interval` seconds have passed since the donor's last donation
+    ///
+    /// A wallet's first donation is always allowed, and a donation exactly `min_interval`
+    /// seconds later is on time.
+    pub fn check_rate_limit(&self, min_interval: u32, now: i64) -> Result<()> {
+        if min_interval > 0
+            && self.donation_count > 0
+            && now.saturating_sub(self.last_donated_at) < i64::from(min_interval)
+        {
+            return Err(ErrorCode::RateLimited.into());
+        }
+        Ok(())
+    }
+
+    /// Records a successful donation of `amount` lamports made at `now`
+    pub fn record_donation(&mut self, campaign: Pubkey, donor: Pubkey, donor_hash: [u8; 32], amount: u64, now: i64, bump: u8) -> Result<()> {
+        self.campaign = campaign;
+        self.donor = donor;
+        self.donor_hash = donor_hash;
+        self.amount_donated = self.amount_donated.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        self.donation_count = self.donation_count.saturating_add(1);
+        self.last_donated_at = now;
+        self.bump = bump;
+        Ok(())
+    }
+}
+
+impl OrgDonation {
+    /// Fixed size of the OrgDonation account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 32 bytes for Pubkey org
+    /// - 8 bytes for u64 amount_donated
+    /// - 8 bytes for u64 donation_count
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+impl CampaignArchive {
+    /// Fixed size of the CampaignArchive account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 32 bytes for Pubkey admin
+    /// - 100 + 2 for name (fixed-size bytes plus u16 length)
+    /// - 1 byte for CampaignStatus status
+    /// - 8 bytes for u64 amount_donated
+    /// - 8 bytes for u64 refunded_amount
+    /// - 8 bytes for u64 donation_count
+    /// - 4 bytes for u32 donor_count
+    /// - 8 bytes for u64 goal
+    /// - 1 byte for bool goal_met
+    /// - 8 bytes for i64 created_at
+    /// - 8 bytes for i64 deadline
+    /// - 8 bytes for i64 archived_at
+    /// - 32 bytes for Pubkey rent_sweep_target
+    pub const LEN: usize = 8 + 32 + 32 + 100 + 2 + 1 + 8 + 8 + 8 + 4 + 8 + 1 + 8 + 8 + 8 + 32;
+
+    /// Snapshots `campaign`, whose address is `key`, as it stands at `now`
+    pub fn record(&mut self, key: Pubkey, campaign: &Campaign, now: i64) {
+        self.campaign = key;
+        self.admin = campaign.admin;
+        self.name = campaign.name;
+        self.name_len = campaign.name_len;
+        self.status = campaign.status;
+        self.amount_donated = campaign.amount_donated;
+        self.refunded_amount = campaign.refunded_amount;
+        self.donation_count = campaign.donation_count;
+        self.donor_count = campaign.donor_count;
+        self.goal = campaign.goal;
+        self.goal_met = campaign.goal_progress() >= campaign.goal;
+        self.created_at = campaign.created_at;
+        self.deadline = campaign.deadline;
+        self.archived_at = now;
+        self.rent_sweep_target = campaign.rent_sweep_target();
+    }
+}
+
+impl Pledge {
+    /// Fixed size of the Pledge account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 32 bytes for Pubkey donor
+    /// - 8 bytes for u64 amount
+    /// - 8 bytes for i64 due_by
+    /// - 8 bytes for i64 created_at
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1;
+}
+
+impl DonationMessage {
+    /// Fixed size of the DonationMessage account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 32 bytes for Pubkey author
+    /// - 8 bytes for u64 sequence
+    /// - 280 + 2 for text (fixed-size bytes plus u16 length)
+    /// - 8 bytes for i64 posted_at
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 8 + MAX_MESSAGE_LEN + 2 + 8 + 1;
+}
+
+impl Attestation {
+    /// Fixed size of the Attestation account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey issuer
+    /// - 32 bytes for Pubkey subject
+    /// - 8 bytes for i64 issued_at
+    /// - 8 bytes for i64 expires_at
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+impl DenylistEntry {
+    /// Fixed size of the DenylistEntry account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey authority
+    /// - 32 bytes for Pubkey subject
+    /// - 8 bytes for i64 flagged_at
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+impl QuarantinedDonation {
+    /// Fixed size of the QuarantinedDonation account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 32 bytes for Pubkey donor
+    /// - 8 bytes for u64 amount
+    /// - 8 bytes for i64 quarantined_at
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+impl WaitlistEntry {
+    /// Fixed size of the WaitlistEntry account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 32 bytes for Pubkey donor
+    /// - 8 bytes for u64 position
+    /// - 8 bytes for i64 joined_at
+    /// - 1 byte for bool promoted
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 1;
+}
+
+impl ProgramConfig {
+    /// Fixed size of the ProgramConfig account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey authority
+    /// - 1 byte for u8 bump
+    /// - 2 bytes for u16 fee_bps
+    /// - 32 bytes for Pubkey treasury
+    pub const LEN: usize = 8 + 32 + 1 + 2 + 32;
+
+    /// Reads the config from its PDA, or returns `None` while `initialize_config` hasn't run
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        if *info.owner != crate::ID {
+            return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+        Ok(Some(Self::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+    }
+}
+
+impl DisputeEscrow {
+    /// Fixed size of the DisputeEscrow account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 8 bytes for u64 amount
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+}
+
+impl AuditLog {
+    /// Fixed size of the AuditLog account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey campaign
+    /// - 8 bytes for u64 log_epoch
+    /// - 8 bytes for u64 head
+    /// - 64 * (32 + 8 + 8 + 1 + 7) bytes for entries
+    pub const LEN: usize = 8 + 32 + 8 + 8 + AUDIT_LOG_CAPACITY * (32 + 8 + 8 + 1 + 7);
+
+    /// Writes an entry at `head`, wrapping around (and bumping `log_epoch`) when the buffer is full
+    pub fn append(&mut self, action: AuditAction, actor: Pubkey, amount: u64, timestamp: i64) {
+        self.entries[self.head as usize] = AuditEntry {
+            actor,
+            amount,
+            timestamp,
+            action: action as u8,
+            padding: [0; 7],
+        };
+        self.head += 1;
+        if self.head as usize == AUDIT_LOG_CAPACITY {
+            self.head = 0;
+            self.log_epoch = self.log_epoch.saturating_add(1);
+        }
+    }
+}
+
+// The zero-copy layout must match the documented size exactly
+const _: () = assert!(AuditLog::LEN == DISCRIMINATOR_LEN + core::mem::size_of::<AuditLog>());
+
+impl Follow {
+    /// Fixed size of the Follow account in bytes
+    /// - 8 bytes for discriminator
+    /// - 32 bytes for Pubkey user
+    /// - 32 bytes for Pubkey campaign
+    /// - 1 byte for u8 bump
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes a zeroed account, as `create_many` does: no fee, goal, deadline or limits
+    fn zeroed_campaign() -> Campaign {
+        Campaign::try_deserialize_unchecked(&mut &[0u8; Campaign::LEN][..]).unwrap()
+    }
+
+    #[test]
+    fn program_accounts_decode_with_the_types_crate() {
+        let mut campaign = zeroed_campaign();
+        campaign.name_len = croudfunding_types::write_padded(&mut campaign.name, "Wells").unwrap();
+        campaign.admin = Pubkey::new_unique();
+        campaign.amount_donated = 1_234;
+        campaign.status = CampaignStatus::Successful;
+        campaign.fee_recipients[1].weight_bps = 3_000;
+        campaign.rent_minimum = Campaign::LEN as u64;
+
+        let mut data = Vec::new();
+        campaign.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Campaign::LEN);
+        assert_eq!(data[..DISCRIMINATOR_LEN], CAMPAIGN_DISCRIMINATOR);
+
+        let decoded = <croudfunding_types::Campaign as types_borsh::BorshDeserialize>::try_from_slice(&data[DISCRIMINATOR_LEN..]).unwrap();
+        assert_eq!(decoded.name(), Some("Wells"));
+        assert_eq!(decoded.admin.to_bytes(), campaign.admin.to_bytes());
+        assert_eq!(decoded.amount_donated, 1_234);
+        assert_eq!(decoded.status, croudfunding_types::CampaignStatus::Successful);
+        assert_eq!(decoded.fee_recipients[1].weight_bps, 3_000);
+        assert_eq!(decoded.rent_minimum, Campaign::LEN as u64);
+    }
+
+    #[test]
+    fn campaign_without_fee_owes_no_split() {
+        let campaign = zeroed_campaign();
+        assert_eq!(campaign.fee_shares(1_000_000).unwrap(), [0, 0]);
+    }
+
+    #[test]
+    fn fee_is_split_between_recipients() {
+        let mut campaign = zeroed_campaign();
+        campaign.fee_bps = 500;
+        campaign.fee_recipients[0].weight_bps = 7_000;
+        campaign.fee_recipients[1].weight_bps = 3_000;
+
+        // 5% of 1_000_001 is 50_000; the first recipient takes the rounding remainder
+        assert_eq!(campaign.fee_shares(1_000_001).unwrap(), [35_000, 15_000]);
+        // A donation too small to owe a fee needs no split either
+        assert_eq!(campaign.fee_shares(19).unwrap(), [0, 0]);
+    }
+
+    #[test]
+    fn allowlisted_campaign_owes_no_platform_fee() {
+        let mut campaign = zeroed_campaign();
+        assert!(campaign.owes_platform_fee());
+        campaign.allowed_destination_count = 1;
+        assert!(!campaign.owes_platform_fee());
+        campaign.allowed_destination_count = 0;
+        campaign.fee_exempt_campaign = true;
+        assert!(!campaign.owes_platform_fee());
+    }
+
+    #[test]
+    fn fee_exempt_campaign_owes_no_split() {
+        let mut campaign = zeroed_campaign();
+        campaign.fee_bps = 500;
+        campaign.fee_exempt_campaign = true;
+        assert_eq!(campaign.fee_shares(1_000_000).unwrap(), [0, 0]);
+    }
+
+    #[test]
+    fn last_donation_is_partially_matched_when_pool_runs_low() {
+        let mut campaign = zeroed_campaign();
+        campaign.match_pool = 151;
+
+        assert_eq!(campaign.record_donation(100, true, 0).unwrap(), 100);
+        assert_eq!(campaign.record_donation(100, true, 0).unwrap(), 51);
+        assert_eq!(campaign.record_donation(100, true, 0).unwrap(), 0);
+
+        // The pool is used up exactly, with every lamport accounted for as matched
+        assert_eq!(campaign.match_pool, 0);
+        assert_eq!(campaign.matched_total, 151);
+        assert_eq!(campaign.amount_donated, 300 + 151);
+    }
+
+    #[test]
+    fn refunds_release_the_endowed_share() {
+        let mut campaign = zeroed_campaign();
+        campaign.endowment_bps = 2_500;
+        campaign.rent_minimum = 1_000;
+        campaign.record_donation(400, true, 0).unwrap();
+        campaign.record_donation(600, true, 0).unwrap();
+        assert_eq!(campaign.endowment_total, 250);
+
+        // The endowment is never spendable by the admin
+        assert_eq!(campaign.spendable_lamports(2_000, 0).unwrap(), 750);
+
+        campaign.record_refund(400).unwrap();
+        assert_eq!(campaign.endowment_total, 150);
+        campaign.record_refund(600).unwrap();
+        assert_eq!(campaign.endowment_total, 0);
+        assert_eq!(campaign.net_raised(), 0);
+    }
+
+    #[test]
+    fn status_only_moves_for_active_campaigns_past_their_deadline() {
+        let mut campaign = zeroed_campaign();
+        campaign.goal = 100;
+        campaign.deadline = 10;
+        campaign.amount_donated = 100;
+
+        assert!(!campaign.recompute_status(9, 0));
+        assert!(campaign.status == CampaignStatus::Active);
+
+        // Paused and cancelled campaigns keep their status past the deadline
+        for status in [CampaignStatus::Paused, CampaignStatus::Cancelled] {
+            campaign.status = status;
+            assert!(!campaign.recompute_status(10, 0));
+            assert!(campaign.status == status);
+        }
+
+        campaign.status = CampaignStatus::Active;
+        assert!(campaign.recompute_status(10, 0));
+        assert!(campaign.status == CampaignStatus::Successful);
+        // Finalizing is one-way: refunds after success don't turn it into a failure
+        campaign.refunded_amount = 100;
+        assert!(!campaign.recompute_status(20, 0));
+        assert!(campaign.status == CampaignStatus::Successful);
+    }
+
+    #[test]
+    fn success_needs_both_the_goal_and_the_donor_minimum() {
+        let mut campaign = zeroed_campaign();
+        campaign.deadline = 10;
+        campaign.goal = 100;
+        campaign.min_donors = 2;
+        campaign.amount_donated = 100;
+        campaign.donor_count = 1;
+        assert!(campaign.recompute_status(10, 0));
+        assert!(campaign.status == CampaignStatus::Failed);
+
+        campaign.status = CampaignStatus::Active;
+        campaign.donor_count = 2;
+        assert!(campaign.recompute_status(10, 0));
+        assert!(campaign.status == CampaignStatus::Successful);
+
+        // A donor-count goal counts donors, not lamports
+        campaign.status = CampaignStatus::Active;
+        campaign.goal_type = GoalType::Donors;
+        campaign.goal = 3;
+        assert!(campaign.recompute_status(10, 0));
+        assert!(campaign.status == CampaignStatus::Failed);
+
+        // Without a deadline a campaign never finalizes
+        let mut open_ended = zeroed_campaign();
+        assert!(!open_ended.recompute_status(i64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn refunds_follow_the_policy_and_status() {
+        let mut campaign = zeroed_campaign();
+        campaign.goal = 100;
+        campaign.deadline = 10;
+        campaign.refund_delay = 5;
+
+        campaign.refund_policy = RefundPolicy::None;
+        assert_eq!(campaign.check_refund_allowed(0, 0).unwrap_err(), ErrorCode::RefundNotAvailable.into());
+        campaign.status = CampaignStatus::Cancelled;
+        assert_eq!(campaign.check_refund_allowed(0, 0).unwrap_err(), ErrorCode::RefundNotAvailable.into());
+
+        campaign.refund_policy = RefundPolicy::UntilDeadline;
+        assert!(campaign.check_refund_allowed(20, 0).is_ok());
+        campaign.status = CampaignStatus::Active;
+        assert!(campaign.check_refund_allowed(9, 0).is_ok());
+        assert_eq!(campaign.check_refund_allowed(10, 0).unwrap_err(), ErrorCode::RefundNotAvailable.into());
+
+        campaign.refund_policy = RefundPolicy::OnFailure;
+        assert_eq!(campaign.check_refund_allowed(12, 0).unwrap_err(), ErrorCode::RefundNotYetOpen.into());
+        assert!(campaign.check_refund_allowed(15, 0).is_ok());
+        campaign.amount_donated = 100;
+        assert_eq!(campaign.check_refund_allowed(15, 0).unwrap_err(), ErrorCode::RefundNotAvailable.into());
+        // Cancelling refunds even a campaign that met its goal
+        campaign.status = CampaignStatus::Cancelled;
+        assert!(campaign.check_refund_allowed(0, 0).is_ok());
+        // A finalized success stays closed to refunds even if its live totals drop below the goal
+        campaign.status = CampaignStatus::Successful;
+        campaign.amount_donated = 50;
+        assert_eq!(campaign.check_refund_allowed(15, 0).unwrap_err(), ErrorCode::RefundNotAvailable.into());
+    }
+
+    #[test]
+    fn all_or_nothing_funds_stay_reserved_until_success() {
+        let mut campaign = zeroed_campaign();
+        campaign.refund_policy = RefundPolicy::OnFailure;
+        campaign.goal = 1_000;
+        campaign.deadline = 100;
+        campaign.amount_donated = 999;
+        assert!(campaign.is_all_or_nothing());
+        assert_eq!(campaign.check_withdrawable(50, 0).unwrap_err(), ErrorCode::FundsReserved.into());
+        // Past the deadline but not yet finalized, the campaign can still fail
+        assert_eq!(campaign.check_withdrawable(150, 0).unwrap_err(), ErrorCode::FundsReserved.into());
+
+        assert!(campaign.recompute_status(150, 0));
+        assert!(campaign.status == CampaignStatus::Failed);
+        assert_eq!(campaign.check_withdrawable(150, 0).unwrap_err(), ErrorCode::CampaignNotActive.into());
+
+        campaign.status = CampaignStatus::Active;
+        campaign.amount_donated = 1_000;
+        assert!(campaign.recompute_status(150, 0));
+        assert!(campaign.status == CampaignStatus::Successful);
+        assert!(campaign.check_withdrawable(150, 0).is_ok());
+
+        // The cliff holds even once the campaign has succeeded
+        campaign.withdraw_cliff = 200;
+        assert_eq!(campaign.check_withdrawable(150, 0).unwrap_err(), ErrorCode::CliffNotReached.into());
+    }
+
+    #[test]
+    fn audit_log_wraps_and_counts_epochs() {
+        let mut log = <AuditLog as bytemuck::Zeroable>::zeroed();
+        let actor = Pubkey::new_unique();
+        for i in 0..AUDIT_LOG_CAPACITY as u64 {
+            log.append(AuditAction::Withdraw, actor, i, 0);
+        }
+        assert_eq!(log.head, 0);
+        assert_eq!(log.log_epoch, 1);
+
+        // The 65th entry overwrites the oldest one and leaves the rest intact
+        log.append(AuditAction::Cancel, actor, 64, 0);
+        assert_eq!(log.head, 1);
+        assert_eq!(log.log_epoch, 1);
+        assert_eq!(log.entries[0].amount, 64);
+        assert_eq!(log.entries[0].action, AuditAction::Cancel as u8);
+        assert_eq!(log.entries[1].amount, 1);
+
+        for i in 65..2 * AUDIT_LOG_CAPACITY as u64 + 3 {
+            log.append(AuditAction::Withdraw, actor, i, 0);
+        }
+        assert_eq!(log.head, 3);
+        assert_eq!(log.log_epoch, 2);
+        assert_eq!(log.entries[2].amount, 2 * AUDIT_LOG_CAPACITY as u64 + 2);
+        assert_eq!(log.entries[3].amount, AUDIT_LOG_CAPACITY as u64 + 3);
+    }
+
+    #[test]
+    fn pending_donations_mature_despite_a_steady_stream() {
+        let mut campaign = zeroed_campaign();
+        campaign.donation_maturity_slots = 10;
+
+        campaign.record_donation(100, true, 0).unwrap();
+        assert_eq!(campaign.pending_lamports(5), 100);
+        // A later donation doesn't hold back the first one
+        campaign.record_donation(50, false, 5).unwrap();
+        assert_eq!(campaign.pending_lamports(9), 150);
+        assert_eq!(campaign.pending_lamports(10), 50);
+        assert_eq!(campaign.pending_lamports(15), 0);
+
+        // Donating every few slots, only the donations of the last two periods are ever pending
+        for slot in (20..200).step_by(3) {
+            campaign.record_donation(1, false, slot).unwrap();
+            assert!(campaign.pending_lamports(slot) <= 20 / 3 + 1);
+        }
+        assert_eq!(campaign.pending_lamports(197 + 10), 0);
+    }
+
+    #[test]
+    fn over_funded_campaign_releases_only_its_surplus() {
+        let mut campaign = zeroed_campaign();
+        campaign.refund_policy = RefundPolicy::OnFailure;
+        campaign.goal = 1_000;
+        campaign.deadline = 100;
+        campaign.amount_donated = 1_500;
+        assert!(campaign.check_overage_withdrawable(50, 500).is_ok());
+        assert_eq!(campaign.check_overage_withdrawable(50, 501).unwrap_err(), ErrorCode::InsufficientFunds.into());
+
+        campaign.amount_withdrawn = 500;
+        assert_eq!(campaign.check_overage_withdrawable(50, 1).unwrap_err(), ErrorCode::NoOverage.into());
+
+        // Without an amount goal the whole balance backs the donor minimum, so none of it is surplus
+        campaign.goal = 0;
+        campaign.min_donors = 3;
+        campaign.amount_withdrawn = 0;
+        assert!(campaign.is_all_or_nothing());
+        assert_eq!(campaign.overage(), 0);
+        assert_eq!(campaign.check_overage_withdrawable(50, 1).unwrap_err(), ErrorCode::NoOverage.into());
+    }
+
+    #[test]
+    fn withdrawals_wait_for_the_goal_or_the_deadline() {
+        let mut campaign = zeroed_campaign();
+        // Open-ended campaigns pay out any time
+        assert!(campaign.check_withdrawable(0, 0).is_ok());
+
+        campaign.goal = 1_000;
+        campaign.deadline = 100;
+        assert!(campaign.check_withdrawable(50, 0).is_err());
+        assert!(campaign.check_withdrawable(100, 0).is_ok());
+
+        // An all-or-nothing campaign that met its goal may pay out before the deadline
+        campaign.refund_policy = RefundPolicy::OnFailure;
+        assert!(campaign.check_withdrawable(100, 0).is_err());
+        campaign.amount_donated = 1_000;
+        assert!(campaign.check_withdrawable(50, 0).is_ok());
+
+        // A failed campaign's funds belong to its donors only if they can claim them back
+        campaign.amount_donated = 0;
+        campaign.status = CampaignStatus::Failed;
+        assert!(campaign.check_withdrawable(200, 0).is_err());
+        campaign.refund_policy = RefundPolicy::None;
+        assert!(campaign.check_withdrawable(200, 0).is_ok());
+        campaign.status = CampaignStatus::Cancelled;
+        assert!(campaign.check_withdrawable(200, 0).is_ok());
+        campaign.refund_policy = RefundPolicy::UntilDeadline;
+        assert!(campaign.check_withdrawable(200, 0).is_err());
+    }
+
+    #[test]
+    fn campaign_is_unfunded_until_someone_puts_lamports_in() {
+        let mut campaign = zeroed_campaign();
+        assert!(campaign.is_unfunded());
+        campaign.pledged_total = 1;
+        assert!(!campaign.is_unfunded());
+        campaign.pledged_total = 0;
+        campaign.match_pool = 1;
+        assert!(!campaign.is_unfunded());
+        campaign.match_pool = 0;
+        campaign.record_donation(1, true, 0).unwrap();
+        assert!(!campaign.is_unfunded());
+    }
+
+    #[test]
+    fn period_withdrawal_cap_resets_at_the_period_boundary() {
+        let mut campaign = zeroed_campaign();
+        campaign.amount_donated = 1_000;
+        campaign.max_withdraw_pct_per_period = 2_500;
+        campaign.withdraw_period = 100;
+        campaign.period_start = 0;
+
+        campaign.record_period_withdrawal(200, 10).unwrap();
+        campaign.record_period_withdrawal(50, 99).unwrap();
+        assert_eq!(campaign.record_period_withdrawal(1, 99).unwrap_err(), ErrorCode::PeriodLimitExceeded.into());
+        assert_eq!(campaign.withdrawn_in_period, 250);
+
+        // The next period starts with the full cap again
+        campaign.record_period_withdrawal(250, 100).unwrap();
+        assert_eq!(campaign.period_start, 100);
+        assert_eq!(campaign.record_period_withdrawal(1, 150).unwrap_err(), ErrorCode::PeriodLimitExceeded.into());
+
+        // Zero bps disables the cap
+        campaign.max_withdraw_pct_per_period = 0;
+        campaign.record_period_withdrawal(u64::MAX, 150).unwrap();
+    }
+
+    #[test]
+    fn lamport_math_fails_instead_of_wrapping() {
+        let mut campaign = zeroed_campaign();
+        campaign.rent_minimum = 100;
+        campaign.match_pool = 50;
+        assert_eq!(campaign.available_lamports(99).unwrap_err(), ErrorCode::AccountNotRentExempt.into());
+        assert_eq!(campaign.available_lamports(149).unwrap_err(), ErrorCode::InsufficientFunds.into());
+        assert_eq!(campaign.available_lamports(150).unwrap(), 0);
+        assert_eq!(campaign.available_lamports(u64::MAX).unwrap(), u64::MAX - 150);
+
+        campaign.match_pool = 0;
+        campaign.amount_donated = u64::MAX;
+        assert_eq!(campaign.record_donation(1, false, 0).unwrap_err(), ErrorCode::Overflow.into());
+        campaign.amount_donated = u64::MAX - 1;
+        campaign.record_donation(1, false, 0).unwrap();
+        assert_eq!(campaign.amount_donated, u64::MAX);
+    }
+
+    #[test]
+    fn move_lamports_checks_both_balances() {
+        let (from_key, to_key, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), crate::ID);
+        let (mut from_lamports, mut to_lamports) = (10u64, u64::MAX - 5);
+        let (mut from_data, mut to_data) = ([0u8; 0], [0u8; 0]);
+        let from = AccountInfo::new(&from_key, false, true, &mut from_lamports, &mut from_data, &owner, false, 0);
+        let to = AccountInfo::new(&to_key, false, true, &mut to_lamports, &mut to_data, &owner, false, 0);
+
+        assert_eq!(move_lamports(&from, &to, 11).unwrap_err(), ErrorCode::InsufficientFunds.into());
+        assert_eq!(move_lamports(&from, &to, 6).unwrap_err(), ErrorCode::Overflow.into());
+        assert_eq!(from.lamports(), 10);
+        move_lamports(&from, &to, 5).unwrap();
+        assert_eq!((from.lamports(), to.lamports()), (5, u64::MAX));
+    }
+
+    #[test]
+    fn rate_limit_allows_donation_at_exact_interval() {
+        let mut record = DonorRecord::try_deserialize_unchecked(&mut &[0u8; DonorRecord::LEN][..]).unwrap();
+        // A wallet's first donation is never limited
+        assert!(record.check_rate_limit(60, 1_000).is_ok());
+
+        record.record_donation(Pubkey::new_unique(), Pubkey::new_unique(), [0; 32], 1, 1_000, 255).unwrap();
+        assert!(record.check_rate_limit(60, 1_059).is_err());
+        assert!(record.check_rate_limit(60, 1_060).is_ok());
+        // Without a limit, back-to-back donations are fine
+        assert!(record.check_rate_limit(0, 1_000).is_ok());
+    }
+
+    #[test]
+    fn legacy_campaign_decodes_original_layout() {
+        let admin = Pubkey::new_unique();
+        let mut data = Campaign::DISCRIMINATOR.to_vec();
+        for text in ["Roof repair", "New roof for the community hall"] {
+            data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            data.extend_from_slice(text.as_bytes());
+        }
+        data.extend_from_slice(&42_000u64.to_le_bytes());
+        data.extend_from_slice(admin.as_ref());
+        data.resize(LegacyCampaign::LEN, 0);
+
+        assert_eq!(LegacyCampaign::LEN, 656);
+        let legacy = LegacyCampaign::deserialize(&mut &data[DISCRIMINATOR_LEN..]).unwrap();
+        assert_eq!(legacy.name, "Roof repair");
+        assert_eq!(legacy.description, "New roof for the community hall");
+        assert_eq!(legacy.amount_donated, 42_000);
+        assert_eq!(legacy.admin, admin);
+    }
+
+    #[test]
+    fn campaign_without_key_keeps_original_address() {
+        let creator = Pubkey::new_unique();
+        let original = Pubkey::find_program_address(&[CAMPAIGN_SEED, creator.as_ref()], &ID);
+        let unkeyed = Pubkey::find_program_address(
+            &[CAMPAIGN_SEED, creator.as_ref(), croudfunding_types::campaign_seed_key(&[0; 16])],
+            &ID,
+        );
+        assert_eq!(unkeyed, original);
+
+        // Retrying with the same key lands on the same address; a different key does not
+        let keyed = |key: [u8; 16]| {
+            Pubkey::find_program_address(&[CAMPAIGN_SEED, creator.as_ref(), croudfunding_types::campaign_seed_key(&key)], &ID).0
+        };
+        assert_eq!(keyed([7; 16]), keyed([7; 16]));
+        assert_ne!(keyed([7; 16]), keyed([8; 16]));
+        assert_ne!(keyed([7; 16]), original.0);
+    }
 }
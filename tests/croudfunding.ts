@@ -0,0 +1,496 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { Croudfunding } from "../target/types/croudfunding";
+
+const { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } = anchor.web3;
+
+describe("croudfunding", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.Croudfunding as Program<Croudfunding>;
+  const creator = provider.wallet.publicKey;
+
+  // Defaults of `CampaignParams`: an open-ended campaign without fees, goal or deadline
+  const campaignParams = (overrides: Record<string, unknown> = {}) => ({
+    name: "Clean water",
+    description: "Wells for the village",
+    minSecondsBetweenDonations: 0,
+    idempotencyKey: Array.from(Keypair.generate().publicKey.toBytes().slice(0, 16)),
+    feeBps: 0,
+    feeRecipients: [
+      { recipient: PublicKey.default, weightBps: 0 },
+      { recipient: PublicKey.default, weightBps: 0 },
+    ],
+    goal: new BN(0),
+    deadline: new BN(0),
+    refundDelay: new BN(0),
+    reopenGracePeriod: new BN(0),
+    refundPolicy: { none: {} },
+    allowedDestinations: [],
+    verificationMin: new BN(0),
+    extraSpace: 0,
+    currencySymbol: "",
+    crankBountyBps: 0,
+    minDonors: 0,
+    requireAttestation: false,
+    attestationIssuer: PublicKey.default,
+    maxDonors: 0,
+    dustThreshold: new BN(0),
+    withdrawCliff: new BN(0),
+    accrueFees: false,
+    hideTotalUntilDeadline: false,
+    listed: true,
+    eventLevel: { full: {} },
+    donationMaturitySlots: new BN(0),
+    goalType: { amount: {} },
+    antiSnipeWindow: new BN(0),
+    antiSnipeExtension: new BN(0),
+    hardGoal: new BN(0),
+    withdrawCondition: PublicKey.default,
+    withdrawConditionOwner: PublicKey.default,
+    endowmentBps: 0,
+    denylist: PublicKey.default,
+    donationStep: new BN(0),
+    deadlineMode: { timestamp: {} },
+    deadlineSlot: new BN(0),
+    rentSweepTarget: PublicKey.default,
+    locale: [0, 0],
+    matchExpiry: new BN(0),
+    thankYou: "",
+    maxWithdrawPctPerPeriod: 0,
+    withdrawPeriod: new BN(0),
+    ...overrides,
+  });
+
+  // The idempotency key only joins the seeds when it isn't all zero
+  const campaignAddress = (key: number[]) => {
+    const seeds = [Buffer.from("campaign"), creator.toBuffer()];
+    if (key.some((byte) => byte !== 0)) {
+      seeds.push(Buffer.from(key));
+    }
+    return PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  };
+
+  const createCampaign = async (params: ReturnType<typeof campaignParams>) => {
+    const campaign = campaignAddress(params.idempotencyKey as number[]);
+    await program.methods
+      .create(params)
+      .accountsPartial({ campaign, user: creator, systemProgram: SystemProgram.programId })
+      .rpc();
+    return campaign;
+  };
+
+  const fundedWallet = async (sol = 2) => {
+    const wallet = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(wallet.publicKey, sol * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature);
+    return wallet;
+  };
+
+  const donate = (campaign: PublicKey, donor: Keypair, lamports: number) =>
+    program.methods
+      .donate(new BN(lamports), null, null, false)
+      .accountsPartial({
+        campaign,
+        user: donor.publicKey,
+        feeRecipientA: null,
+        feeRecipientB: null,
+        orgDonation: null,
+        attestation: null,
+        denylistEntry: null,
+        quarantine: null,
+        waitlistEntry: null,
+      })
+      .signers([donor])
+      .rpc();
+
+  const withdraw = (campaign: PublicKey, lamports: number) =>
+    program.methods
+      .withdraw(new BN(lamports), null)
+      .accountsPartial({
+        campaign,
+        admin: creator,
+        treasury: null,
+        destination: null,
+        disputeEscrow: null,
+        withdrawCondition: null,
+      })
+      .rpc();
+
+  // Splits `lamports` across `campaigns` by `weights`, passing each campaign with the donor's record on it
+  const donateWeighted = (campaigns: PublicKey[], weights: number[], donor: Keypair, lamports: number) =>
+    program.methods
+      .donateWeighted(new BN(lamports), weights)
+      .accountsPartial({ user: donor.publicKey })
+      .remainingAccounts(
+        campaigns.flatMap((campaign) => [
+          { pubkey: campaign, isSigner: false, isWritable: true },
+          {
+            pubkey: PublicKey.findProgramAddressSync(
+              [Buffer.from("donor"), campaign.toBuffer(), donor.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            isSigner: false,
+            isWritable: true,
+          },
+        ])
+      )
+      .signers([donor])
+      .rpc();
+
+  // Awaits a transaction that must fail with the program error `code`
+  const expectError = async (tx: Promise<unknown>, code: string) => {
+    try {
+      await tx;
+    } catch (err) {
+      assert.equal((err as anchor.AnchorError).error?.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code}`);
+  };
+
+  // The config singleton makes the provider wallet, the program's upgrade authority, its authority
+  const ensureConfig = async () => {
+    const [config] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+    if (await program.account.programConfig.fetchNullable(config)) {
+      return;
+    }
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      .initializeConfig()
+      .accountsPartial({ authority: creator, program: program.programId, programData })
+      .rpc();
+  };
+
+  it("rejects a double-submitted create instead of making a second campaign", async () => {
+    const params = campaignParams();
+    const campaign = await createCampaign(params);
+
+    await expectError(createCampaign(params), "AlreadyCreated");
+
+    const account = await program.account.campaign.fetch(campaign);
+    assert.ok(account.admin.equals(creator));
+    assert.equal(account.donationCount.toNumber(), 0);
+  });
+
+  it("keeps an all-or-nothing campaign's donations reserved before it succeeds", async () => {
+    const campaign = await createCampaign(
+      campaignParams({
+        refundPolicy: { onFailure: {} },
+        goal: new BN(LAMPORTS_PER_SOL),
+        deadline: new BN(Math.floor(Date.now() / 1000) + 3600),
+      })
+    );
+    await donate(campaign, await fundedWallet(), LAMPORTS_PER_SOL / 10);
+
+    await expectError(withdraw(campaign, LAMPORTS_PER_SOL / 20), "FundsReserved");
+  });
+
+  it("matches the last donation only partially when the pool runs low", async () => {
+    const campaign = await createCampaign(campaignParams());
+    const sponsor = await fundedWallet();
+    await program.methods
+      .fundMatchPool(new BN(151))
+      .accountsPartial({ campaign, sponsor: sponsor.publicKey })
+      .signers([sponsor])
+      .rpc();
+
+    const donor = await fundedWallet();
+    for (let i = 0; i < 3; i++) {
+      await donate(campaign, donor, 100);
+    }
+
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.matchedTotal.toNumber(), 151);
+    assert.equal(account.matchPool.toNumber(), 0);
+    assert.equal(account.amountDonated.toNumber(), 300 + 151);
+  });
+
+  it("publishes a hidden donation and its match without their amounts", async () => {
+    const campaign = await createCampaign(campaignParams());
+    const sponsor = await fundedWallet();
+    await program.methods
+      .fundMatchPool(new BN(LAMPORTS_PER_SOL / 10))
+      .accountsPartial({ campaign, sponsor: sponsor.publicKey })
+      .signers([sponsor])
+      .rpc();
+
+    const donor = await fundedWallet();
+    await program.methods
+      .setAmountVisibility(true)
+      .accountsPartial({ campaign, user: donor.publicKey })
+      .signers([donor])
+      .rpc();
+
+    const received: { amount: BN; amountHidden: boolean }[] = [];
+    const matched: { amount: BN; amountHidden: boolean; remainingPool: BN }[] = [];
+    const listeners = [
+      program.addEventListener("donationReceived", (event) => received.push(event)),
+      program.addEventListener("donationMatched", (event) => matched.push(event)),
+    ];
+    try {
+      await donate(campaign, donor, LAMPORTS_PER_SOL / 100);
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+    } finally {
+      await Promise.all(listeners.map((listener) => program.removeEventListener(listener)));
+    }
+
+    assert.equal(received.length, 1);
+    assert.isTrue(received[0].amountHidden);
+    assert.equal(received[0].amount.toNumber(), 0);
+    assert.equal(matched.length, 1);
+    assert.isTrue(matched[0].amountHidden);
+    assert.equal(matched[0].amount.toNumber(), 0);
+    assert.equal(matched[0].remainingPool.toNumber(), 0);
+
+    // The campaign total still counts the donation and its match
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.amountDonated.toNumber(), (2 * LAMPORTS_PER_SOL) / 100);
+  });
+
+  it("merges one campaign into another as a donation from the source", async () => {
+    const source = await createCampaign(campaignParams());
+    const destination = await createCampaign(campaignParams());
+    await donate(source, await fundedWallet(), LAMPORTS_PER_SOL / 10);
+
+    await program.methods
+      .transferToCampaign()
+      .accountsPartial({ source, destination, user: creator })
+      .rpc();
+
+    const [donorRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("donor"), destination.toBuffer(), source.toBuffer()],
+      program.programId
+    );
+    const record = await program.account.donorRecord.fetch(donorRecord);
+    assert.ok(record.donor.equals(source));
+    assert.equal(record.amountDonated.toNumber(), LAMPORTS_PER_SOL / 10);
+
+    const merged = await program.account.campaign.fetch(destination);
+    assert.equal(merged.amountDonated.toNumber(), LAMPORTS_PER_SOL / 10);
+    assert.equal(merged.donorCount, 1);
+    const emptied = await program.account.campaign.fetch(source);
+    assert.equal(emptied.amountWithdrawn.toNumber(), LAMPORTS_PER_SOL / 10);
+  });
+
+  it("credits a direct deposit as an anonymous donation when synced", async () => {
+    const campaign = await createCampaign(campaignParams({ endowmentBps: 1000 }));
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({ fromPubkey: creator, toPubkey: campaign, lamports: LAMPORTS_PER_SOL / 10 })
+      )
+    );
+
+    await program.methods.syncDonated().accountsPartial({ campaign, user: creator }).rpc();
+
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.amountDonated.toNumber(), LAMPORTS_PER_SOL / 10);
+    assert.equal(account.endowmentTotal.toNumber(), LAMPORTS_PER_SOL / 100);
+    assert.equal(account.donorCount, 0);
+
+    // Nothing is left to credit, so a second sync can't inflate the total
+    await expectError(
+      program.methods.syncDonated().accountsPartial({ campaign, user: creator }).rpc(),
+      "NothingToSync"
+    );
+  });
+
+  it("merges a donor's old wallet into their new one", async () => {
+    const campaign = await createCampaign(campaignParams());
+    const oldWallet = await fundedWallet();
+    const newWallet = await fundedWallet();
+    await donate(campaign, oldWallet, LAMPORTS_PER_SOL / 10);
+    await donate(campaign, newWallet, LAMPORTS_PER_SOL / 20);
+
+    await program.methods
+      .mergeDonations()
+      .accountsPartial({ campaign, oldDonor: oldWallet.publicKey, newDonor: newWallet.publicKey })
+      .signers([oldWallet, newWallet])
+      .rpc();
+
+    const donorRecord = (wallet: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("donor"), campaign.toBuffer(), wallet.toBuffer()],
+        program.programId
+      )[0];
+    const merged = await program.account.donorRecord.fetch(donorRecord(newWallet.publicKey));
+    assert.equal(merged.amountDonated.toNumber(), (3 * LAMPORTS_PER_SOL) / 20);
+    assert.equal(merged.donationCount.toNumber(), 2);
+    assert.isNull(await program.account.donorRecord.fetchNullable(donorRecord(oldWallet.publicKey)));
+
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.donorCount, 1);
+  });
+
+  describe("disputes", () => {
+    const escrowAddress = (campaign: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("dispute_escrow"), campaign.toBuffer()], program.programId)[0];
+
+    // A keep-it-all campaign with 0.1 SOL donated, put under dispute by the config authority
+    const disputedCampaign = async (overrides: Record<string, unknown> = {}) => {
+      await ensureConfig();
+      const campaign = await createCampaign(campaignParams(overrides));
+      await donate(campaign, await fundedWallet(), LAMPORTS_PER_SOL / 10);
+      await program.methods.raiseDispute().accountsPartial({ campaign, authority: creator }).rpc();
+      return campaign;
+    };
+
+    const withdrawToEscrow = (campaign: PublicKey, lamports: number) =>
+      program.methods
+        .withdraw(new BN(lamports), null)
+        .accountsPartial({
+          campaign,
+          admin: creator,
+          treasury: null,
+          destination: null,
+          disputeEscrow: escrowAddress(campaign),
+          withdrawCondition: null,
+        })
+        .rpc();
+
+    const resolve = (campaign: PublicKey, toAdmin: boolean) =>
+      program.methods
+        .resolveDispute(toAdmin)
+        .accountsPartial({ campaign, authority: creator, disputeEscrow: escrowAddress(campaign), admin: creator })
+        .rpc();
+
+    it("raises a dispute only once", async () => {
+      const campaign = await disputedCampaign();
+
+      assert.isTrue((await program.account.campaign.fetch(campaign)).disputed);
+      await expectError(
+        program.methods.raiseDispute().accountsPartial({ campaign, authority: creator }).rpc(),
+        "CampaignDisputed"
+      );
+    });
+
+    it("holds a disputed campaign's withdrawals in escrow", async () => {
+      const campaign = await disputedCampaign();
+
+      await expectError(withdraw(campaign, LAMPORTS_PER_SOL / 20), "DisputeEscrowRequired");
+      await withdrawToEscrow(campaign, LAMPORTS_PER_SOL / 20);
+
+      const escrow = await program.account.disputeEscrow.fetch(escrowAddress(campaign));
+      assert.ok(escrow.campaign.equals(campaign));
+      assert.equal(escrow.amount.toNumber(), LAMPORTS_PER_SOL / 20);
+    });
+
+    it("releases the escrow to the admin and closes it", async () => {
+      const campaign = await disputedCampaign();
+      await withdrawToEscrow(campaign, LAMPORTS_PER_SOL / 20);
+
+      await resolve(campaign, true);
+
+      assert.isNull(await program.account.disputeEscrow.fetchNullable(escrowAddress(campaign)));
+      const account = await program.account.campaign.fetch(campaign);
+      assert.isFalse(account.disputed);
+      assert.equal(account.amountWithdrawn.toNumber(), LAMPORTS_PER_SOL / 20);
+    });
+
+    it("returns the escrow to the campaign and opens refunds", async () => {
+      const campaign = await disputedCampaign();
+      await withdrawToEscrow(campaign, LAMPORTS_PER_SOL / 20);
+
+      await resolve(campaign, false);
+
+      assert.isNull(await program.account.disputeEscrow.fetchNullable(escrowAddress(campaign)));
+      const account = await program.account.campaign.fetch(campaign);
+      assert.isFalse(account.disputed);
+      assert.deepEqual(account.status, { cancelled: {} });
+      assert.deepEqual(account.refundPolicy, { onFailure: {} });
+      assert.equal(account.amountWithdrawn.toNumber(), 0);
+
+      // The funds are reserved for refunds, so the admin can't take them once the dispute is over
+      await expectError(withdraw(campaign, LAMPORTS_PER_SOL / 20), "CampaignNotActive");
+    });
+  });
+
+  it("lets an over-funded all-or-nothing campaign withdraw only its surplus early", async () => {
+    const campaign = await createCampaign(
+      campaignParams({
+        refundPolicy: { onFailure: {} },
+        goal: new BN(LAMPORTS_PER_SOL / 10),
+        deadline: new BN(Math.floor(Date.now() / 1000) + 3600),
+      })
+    );
+    await donate(campaign, await fundedWallet(), (3 * LAMPORTS_PER_SOL) / 20);
+
+    const withdrawOverage = (lamports: number) =>
+      program.methods
+        .withdrawOverage(new BN(lamports), null)
+        .accountsPartial({
+          campaign,
+          admin: creator,
+          treasury: null,
+          destination: null,
+          disputeEscrow: null,
+          withdrawCondition: null,
+        })
+        .rpc();
+
+    await expectError(withdrawOverage(LAMPORTS_PER_SOL / 20 + 1), "InsufficientFunds");
+    await withdrawOverage(LAMPORTS_PER_SOL / 20);
+    await expectError(withdrawOverage(1), "NoOverage");
+
+    const account = await program.account.campaign.fetch(campaign);
+    assert.equal(account.amountWithdrawn.toNumber(), LAMPORTS_PER_SOL / 20);
+  });
+
+  describe("batches", () => {
+    const createMany = (params: ReturnType<typeof campaignParams>[]) =>
+      program.methods
+        .createMany(params)
+        .accountsPartial({ user: creator })
+        .remainingAccounts(
+          params.map((p) => ({
+            pubkey: campaignAddress(p.idempotencyKey as number[]),
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .rpc();
+
+    it("rejects an empty create_many batch", async () => {
+      await expectError(createMany([]), "EmptyBatch");
+    });
+
+    it("creates a single-campaign batch", async () => {
+      const params = campaignParams();
+      await createMany([params]);
+
+      const account = await program.account.campaign.fetch(campaignAddress(params.idempotencyKey as number[]));
+      assert.ok(account.admin.equals(creator));
+    });
+
+    it("rejects a weighted donation without campaigns", async () => {
+      await expectError(donateWeighted([], [], await fundedWallet(), LAMPORTS_PER_SOL / 10), "EmptyBatch");
+    });
+
+    it("gives a single-campaign weighted donation the whole gift", async () => {
+      const campaign = await createCampaign(campaignParams());
+      await donateWeighted([campaign], [10_000], await fundedWallet(), LAMPORTS_PER_SOL / 10);
+
+      const account = await program.account.campaign.fetch(campaign);
+      assert.equal(account.amountDonated.toNumber(), LAMPORTS_PER_SOL / 10);
+    });
+  });
+
+  it("splits one gift 50/30/20 across three campaigns", async () => {
+    const campaigns = [];
+    for (let i = 0; i < 3; i++) {
+      campaigns.push(await createCampaign(campaignParams()));
+    }
+
+    // 1_000_001 doesn't divide evenly, so the first campaign also gets the 1-lamport remainder
+    await donateWeighted(campaigns, [5_000, 3_000, 2_000], await fundedWallet(), 1_000_001);
+
+    const credited = await Promise.all(
+      campaigns.map(async (campaign) => (await program.account.campaign.fetch(campaign)).amountDonated.toNumber())
+    );
+    assert.deepEqual(credited, [500_001, 300_000, 200_000]);
+  });
+});